@@ -1,6 +1,8 @@
+use crate::fields::RuleFields;
 use rfd::FileDialog;
-use rulery::{CheckedGameRules, UncheckedGameRules, expr::boolean::BoolExpr};
+use rulery::{CheckedGameRules, UncheckedGameRules, schema};
 use slint::{SharedString, ToSharedString};
+use std::{cell::RefCell, fs, rc::Rc};
 
 slint::include_modules!();
 
@@ -12,12 +14,16 @@ impl App {
     pub fn new() -> Self {
         let ui = AppWindow::new().unwrap();
 
+        // The opened rules, the ui edits are applied onto them
+        let loaded = Rc::new(RefCell::new(UncheckedGameRules::default()));
+
         ui.on_open_rules_clicked({
             let ui = ui.as_weak();
+            let loaded = loaded.clone();
             move || {
                 // Open file dialog
                 let Some(path) = FileDialog::new()
-                    .add_filter("RON file", &["ron"])
+                    .add_filter("Rules file", &["ron", "json", "toml"])
                     .pick_file()
                 else {
                     // Cancelled
@@ -25,14 +31,21 @@ impl App {
                 };
 
                 // Load and check rules
-                let checked =
-                    match UncheckedGameRules::load(&path).and_then(|unchecked| unchecked.check()) {
-                        Ok(rules) => rules,
-                        Err(err) => {
-                            Self::show_dialog("Open Rule Failed".into(), err.to_shared_string());
-                            return;
-                        }
-                    };
+                let unchecked = match UncheckedGameRules::load(&path) {
+                    Ok(rules) => rules,
+                    Err(err) => {
+                        Self::show_dialog("Open Rule Failed".into(), err.to_shared_string());
+                        return;
+                    }
+                };
+
+                let checked = match unchecked.clone().check() {
+                    Ok(rules) => rules,
+                    Err(err) => {
+                        Self::show_dialog("Open Rule Failed".into(), err.to_shared_string());
+                        return;
+                    }
+                };
 
                 let ui = ui.upgrade().unwrap();
 
@@ -42,6 +55,8 @@ impl App {
                     return;
                 }
 
+                *loaded.borrow_mut() = unchecked;
+
                 // Show editor ui
                 ui.set_show_launcher(false);
             }
@@ -49,6 +64,7 @@ impl App {
 
         ui.on_create_rules_clicked({
             let ui = ui.as_weak();
+            let loaded = loaded.clone();
             move || {
                 // Create a default rules
                 let rules = CheckedGameRules::default();
@@ -60,6 +76,8 @@ impl App {
                     return;
                 }
 
+                *loaded.borrow_mut() = UncheckedGameRules::default();
+
                 // Show editor ui
                 ui.set_show_launcher(false);
             }
@@ -69,17 +87,20 @@ impl App {
             let ui = ui.as_weak();
             move || {
                 // Collect rules from ui
-                let rules = match Self::collect_rules_from_ui(ui.upgrade().unwrap()) {
-                    Ok(rules) => rules,
-                    Err(err) => {
-                        Self::show_dialog("Invalid Rules".into(), err.to_shared_string());
-                        return;
-                    }
-                };
+                let rules =
+                    match Self::collect_rules_from_ui(ui.upgrade().unwrap(), &loaded.borrow()) {
+                        Ok(rules) => rules,
+                        Err(err) => {
+                            Self::show_dialog("Invalid Rules".into(), err.to_shared_string());
+                            return;
+                        }
+                    };
 
                 // Open file dialog
                 let Some(path) = FileDialog::new()
                     .add_filter("RON file", &["ron"])
+                    .add_filter("JSON file", &["json"])
+                    .add_filter("TOML file", &["toml"])
                     .set_file_name("untitled.ron")
                     .save_file()
                 else {
@@ -94,6 +115,23 @@ impl App {
             }
         });
 
+        ui.on_export_schema_activated(|| {
+            // Open file dialog
+            let Some(path) = FileDialog::new()
+                .add_filter("JSON Schema", &["json"])
+                .set_file_name("rules.schema.json")
+                .save_file()
+            else {
+                // Cancelled
+                return;
+            };
+
+            // Save schema
+            if let Err(err) = schema::json_schema().and_then(|json| Ok(fs::write(&path, json)?)) {
+                Self::show_dialog("Export Failed".into(), err.to_shared_string());
+            }
+        });
+
         Self { ui }
     }
 
@@ -101,68 +139,60 @@ impl App {
         self.ui.run()
     }
 
-    fn collect_rules_from_ui(ui: AppWindow) -> Result<CheckedGameRules, String> {
-        let mut unchecked = UncheckedGameRules::default();
-
-        // Name
-        unchecked.set_name(ui.get_rules_name().to_string());
-
-        // Board
-        let Ok(rows) = ui.get_board_rows().parse() else {
-            return Err("invalid board rows".to_string());
+    /// Applies the edits in the ui onto a copy of the loaded rules.
+    fn collect_rules_from_ui(
+        ui: AppWindow,
+        loaded: &UncheckedGameRules,
+    ) -> Result<CheckedGameRules, String> {
+        let fields = RuleFields {
+            name: ui.get_rules_name().into(),
+            metadata: ui.get_metadata().into(),
+            board_rows: ui.get_board_rows().into(),
+            board_cols: ui.get_board_cols().into(),
+            pieces: ui.get_pieces().into(),
+            players: ui.get_players().into(),
+            teams: ui.get_teams().into(),
+            initial_layout: ui.get_initial_layout().into(),
+            draw_condition: ui.get_draw_condition().into(),
+            game_over_condition: ui.get_game_over_condition().into(),
         };
 
-        let Ok(cols) = ui.get_board_cols().parse() else {
-            return Err("invalid board columns".to_string());
-        };
-
-        unchecked.set_board_rows(rows);
-        unchecked.set_board_cols(cols);
-
-        // Pieces
-        unchecked
-            .set_pieces_from_ron_str(&ui.get_pieces())
-            .map_err(|err| err.to_string())?;
-
-        // Players
-        unchecked
-            .set_players_from_ron_str(&ui.get_players())
-            .map_err(|err| err.to_string())?;
-
-        // Initial Layout
-        unchecked
-            .set_initial_layout_from_ron_str(&ui.get_initial_layout())
-            .map_err(|err| err.to_string())?;
-
-        // Game over condition
-        let cond = BoolExpr::from_ron_str(&ui.get_game_over_condition())
-            .map_err(|err| format!("Game Over Condition: {}", err))?;
-
-        unchecked.set_game_over_condition(cond);
+        let mut unchecked = loaded.clone();
+        fields.apply(&mut unchecked)?;
 
         unchecked.check().map_err(|err| err.to_string())
     }
 
     fn set_ui_from_rules(ui: &AppWindow, rules: &CheckedGameRules) -> Result<(), String> {
-        let pieces = rules.pieces_to_ron_str().map_err(|err| err.to_string())?;
-
-        let players = rules.players_to_ron_str().map_err(|err| err.to_string())?;
-
-        let initial_layout = rules
-            .initial_layout_to_ron_str()
-            .map_err(|err| err.to_string())?;
-
-        let game_over_condition = rules
-            .game_over_condition_to_ron_str()
-            .map_err(|err| err.to_string())?;
-
-        ui.set_rules_name((rules.name()).into());
-        ui.set_board_rows(rules.board_rows().to_string().into());
-        ui.set_board_cols(rules.board_cols().to_string().into());
-        ui.set_pieces(pieces.into());
-        ui.set_players(players.into());
-        ui.set_initial_layout(initial_layout.into());
-        ui.set_game_over_condition(game_over_condition.into());
+        let fields = RuleFields::from_rules(rules)?;
+
+        ui.set_rules_name(fields.name.into());
+        ui.set_metadata(fields.metadata.into());
+        ui.set_board_rows(fields.board_rows.into());
+        ui.set_board_cols(fields.board_cols.into());
+        ui.set_pieces(fields.pieces.into());
+        ui.set_players(fields.players.into());
+        ui.set_teams(fields.teams.into());
+        ui.set_initial_layout(fields.initial_layout.into());
+        ui.set_draw_condition(fields.draw_condition.into());
+        ui.set_game_over_condition(fields.game_over_condition.into());
+
+        // Inherited fields of a variant
+        let base_rules = rules
+            .extends()
+            .map(|base| base.display().to_string())
+            .unwrap_or_default();
+
+        ui.set_base_rules(base_rules.into());
+        ui.set_rules_name_inherited(rules.is_inherited("name"));
+        ui.set_metadata_inherited(rules.is_inherited("metadata"));
+        ui.set_board_inherited(rules.is_inherited("board"));
+        ui.set_pieces_inherited(rules.is_inherited("pieces"));
+        ui.set_players_inherited(rules.is_inherited("players"));
+        ui.set_teams_inherited(rules.is_inherited("teams"));
+        ui.set_initial_layout_inherited(rules.is_inherited("initial_layout"));
+        ui.set_draw_condition_inherited(rules.is_inherited("draw_condition"));
+        ui.set_game_over_condition_inherited(rules.is_inherited("game_over_condition"));
 
         Ok(())
     }
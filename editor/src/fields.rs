@@ -0,0 +1,187 @@
+use rulery::{
+    CheckedGameRules, UncheckedGameRules, expr::boolean::BoolExpr, locale::LocalizedText,
+    metadata::Metadata,
+};
+
+/// The parts of the rules edited in the ui, as text.
+///
+/// Everything else is kept from the loaded rules when the edits are applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleFields {
+    pub name: String,
+    pub metadata: String,
+    pub board_rows: String,
+    pub board_cols: String,
+    pub pieces: String,
+    pub players: String,
+    pub teams: String,
+    pub initial_layout: String,
+    pub draw_condition: String,
+    pub game_over_condition: String,
+}
+
+impl RuleFields {
+    /// Writes the edited parts of the rules as text.
+    pub fn from_rules(rules: &CheckedGameRules) -> Result<Self, String> {
+        // Name, either plain or a ron map from locale to name
+        let name = match rules.names() {
+            LocalizedText::Plain(name) => name.clone(),
+            names => names.to_ron_str().map_err(|err| err.to_string())?,
+        };
+
+        Ok(Self {
+            name,
+            metadata: rules
+                .metadata()
+                .to_ron_str()
+                .map_err(|err| err.to_string())?,
+            board_rows: rules.board_rows().to_string(),
+            board_cols: rules.board_cols().to_string(),
+            pieces: rules.pieces_to_ron_str().map_err(|err| err.to_string())?,
+            players: rules.players_to_ron_str().map_err(|err| err.to_string())?,
+            teams: rules.teams_to_ron_str().map_err(|err| err.to_string())?,
+            initial_layout: rules
+                .initial_layout_to_ron_str()
+                .map_err(|err| err.to_string())?,
+            draw_condition: rules
+                .draw_condition_to_ron_str()
+                .map_err(|err| err.to_string())?,
+            game_over_condition: rules
+                .game_over_condition_to_ron_str()
+                .map_err(|err| err.to_string())?,
+        })
+    }
+
+    /// Applies the edited parts onto the loaded rules.
+    pub fn apply(&self, rules: &mut UncheckedGameRules) -> Result<(), String> {
+        // Name
+        if self.name.trim_start().starts_with('{') {
+            let names =
+                LocalizedText::from_ron_str(&self.name).map_err(|err| format!("Name: {}", err))?;
+
+            rules.set_name(names);
+        } else {
+            rules.set_name(self.name.clone());
+        }
+
+        // Metadata
+        let metadata =
+            Metadata::from_ron_str(&self.metadata).map_err(|err| format!("Metadata: {}", err))?;
+
+        rules.set_metadata(metadata);
+
+        // Board
+        let Ok(rows) = self.board_rows.parse() else {
+            return Err("invalid board rows".to_string());
+        };
+
+        let Ok(cols) = self.board_cols.parse() else {
+            return Err("invalid board columns".to_string());
+        };
+
+        rules.set_board_rows(rows);
+        rules.set_board_cols(cols);
+
+        // Pieces
+        rules
+            .set_pieces_from_ron_str(&self.pieces)
+            .map_err(|err| err.to_string())?;
+
+        // Players
+        rules
+            .set_players_from_ron_str(&self.players)
+            .map_err(|err| err.to_string())?;
+
+        // Teams
+        rules
+            .set_teams_from_ron_str(&self.teams)
+            .map_err(|err| err.to_string())?;
+
+        // Initial Layout
+        rules
+            .set_initial_layout_from_ron_str(&self.initial_layout)
+            .map_err(|err| err.to_string())?;
+
+        // Draw condition
+        let cond = BoolExpr::from_ron_str(&self.draw_condition)
+            .map_err(|err| format!("Draw Condition: {}", err))?;
+
+        rules.set_draw_condition(cond);
+
+        // Game over condition
+        let cond = BoolExpr::from_ron_str(&self.game_over_condition)
+            .map_err(|err| format!("Game Over Condition: {}", err))?;
+
+        rules.set_game_over_condition(cond);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rulery::format::Format;
+    use std::{fs, path::PathBuf};
+
+    /// Rules using fields the ui does not edit.
+    const RULES: &str = r#"(
+    name: "Round Trip",
+    board: (rows: 6, cols: 6, topology: Hex, wrap: Cols),
+    pieces: {
+        Cube: (count: Finite(3), movement: True, placement: True),
+    },
+    players: {
+        White: (lose_condition: False, win_condition: False),
+        Black: (lose_condition: False, win_condition: False),
+    },
+    initial_layout: [],
+    capture_policy: ReturnToOwner,
+    turn_schedule: (order: Snake, actions_per_turn: [1, 2], dice: Some((count: 2, faces: 6))),
+    time_control: Some((initial_millis: 60000, increment_millis: 1000)),
+    stacking: TopPiece,
+    tie_break: PlayerOrder,
+    game_over_condition: False,
+)"#;
+
+    /// Shows the rules in the ui and saves them back without edits.
+    fn round_trip(unchecked: UncheckedGameRules, name: &str) -> (String, String) {
+        let loaded = unchecked.clone();
+        let fields = RuleFields::from_rules(&unchecked.check().unwrap()).unwrap();
+
+        let mut edited = loaded.clone();
+        fields.apply(&mut edited).unwrap();
+
+        let dir = std::env::temp_dir();
+        let before = dir.join(format!("editor-{name}-before-{}.ron", std::process::id()));
+        let after = dir.join(format!("editor-{name}-after-{}.ron", std::process::id()));
+
+        loaded.check().unwrap().save(&before).unwrap();
+        edited.check().unwrap().save(&after).unwrap();
+
+        (
+            fs::read_to_string(before).unwrap(),
+            fs::read_to_string(after).unwrap(),
+        )
+    }
+
+    #[test]
+    fn unedited_fields_survive_a_round_trip() {
+        let unchecked = UncheckedGameRules::parse(RULES, Format::Ron).unwrap();
+        let (before, after) = round_trip(unchecked, "fields");
+
+        assert_eq!(before, after);
+        assert!(after.contains("ReturnToOwner"));
+        assert!(after.contains("Snake"));
+        assert!(after.contains("TopPiece"));
+        assert!(after.contains("60000"));
+    }
+
+    #[test]
+    fn example_rules_survive_a_round_trip() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../example-rules/xiangqi.ron");
+        let (before, after) = round_trip(UncheckedGameRules::load(path).unwrap(), "xiangqi");
+
+        assert_eq!(before, after);
+    }
+}
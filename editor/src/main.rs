@@ -1,6 +1,7 @@
 use crate::app::App;
 
 mod app;
+mod fields;
 
 fn main() -> Result<(), slint::PlatformError> {
     App::new().run()
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 pub struct CommonMaterials {
     pub tile_black: Handle<StandardMaterial>,
     pub tile_white: Handle<StandardMaterial>,
+    pub tile_gray: Handle<StandardMaterial>,
     pub highlight_source_or_target: Handle<StandardMaterial>,
     pub highlight_placeable: Handle<StandardMaterial>,
 }
@@ -13,6 +14,7 @@ impl CommonMaterials {
         CommonMaterials {
             tile_black: materials.add(Color::srgb(0.4, 0.4, 0.4)),
             tile_white: materials.add(Color::srgb(0.8, 0.8, 0.8)),
+            tile_gray: materials.add(Color::srgb(0.6, 0.6, 0.6)),
             highlight_source_or_target: materials.add(StandardMaterial {
                 base_color: Color::srgba(0.4, 0.6, 1.0, 0.5),
                 alpha_mode: AlphaMode::Blend,
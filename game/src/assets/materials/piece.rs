@@ -4,28 +4,86 @@ use bevy::{
         Color,
         palettes::tailwind::{CYAN_300, GREEN_300, PURPLE_300, RED_300, YELLOW_300},
     },
+    image::Image,
     pbr::StandardMaterial,
 };
-use rulery::piece::PieceColor;
+use rulery::{
+    CheckedGameRules,
+    piece::{BuiltinColor, PieceColor, PieceModel, Rgb},
+};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct PieceMaterials {
+    builtin: HashMap<BuiltinColor, Handle<StandardMaterial>>,
+
+    /// Materials of the players declared by the loaded rules.
     map: HashMap<PieceColor, Handle<StandardMaterial>>,
+
+    /// Materials of piece kinds with a texture, per player.
+    textured: HashMap<(PieceColor, PieceModel), Handle<StandardMaterial>>,
 }
 
 impl PieceMaterials {
     pub fn new(materials: &mut Assets<StandardMaterial>) -> Self {
         PieceMaterials {
-            map: HashMap::from([
-                (PieceColor::White, materials.add(Color::srgb(0.8, 0.8, 0.8))),
-                (PieceColor::Black, materials.add(Color::srgb(0.3, 0.3, 0.3))),
-                (PieceColor::Red, materials.add(Color::from(RED_300))),
-                (PieceColor::Yellow, materials.add(Color::from(YELLOW_300))),
-                (PieceColor::Green, materials.add(Color::from(GREEN_300))),
-                (PieceColor::Cyan, materials.add(Color::from(CYAN_300))),
-                (PieceColor::Purple, materials.add(Color::from(PURPLE_300))),
+            builtin: HashMap::from([
+                (
+                    BuiltinColor::White,
+                    materials.add(Color::srgb(0.8, 0.8, 0.8)),
+                ),
+                (
+                    BuiltinColor::Black,
+                    materials.add(Color::srgb(0.3, 0.3, 0.3)),
+                ),
+                (BuiltinColor::Red, materials.add(Color::from(RED_300))),
+                (BuiltinColor::Yellow, materials.add(Color::from(YELLOW_300))),
+                (BuiltinColor::Green, materials.add(Color::from(GREEN_300))),
+                (BuiltinColor::Cyan, materials.add(Color::from(CYAN_300))),
+                (BuiltinColor::Purple, materials.add(Color::from(PURPLE_300))),
             ]),
+            map: HashMap::new(),
+            textured: HashMap::new(),
+        }
+    }
+
+    /// Maps the players declared by the rules to materials.
+    ///
+    /// Players with an exact color get their own material.
+    pub fn bind(&mut self, rules: &CheckedGameRules, materials: &mut Assets<StandardMaterial>) {
+        self.map = rules
+            .players()
+            .map(|(color, _)| {
+                let material = match rules.color_rgb(color) {
+                    Some(Rgb(r, g, b)) => materials.add(Color::srgb_u8(r, g, b)),
+                    None => self.builtin[&rules.color_visual(color)].clone(),
+                };
+
+                (color, material)
+            })
+            .collect();
+    }
+
+    /// Adds a texture for a piece kind, tinted with each player's color.
+    pub fn set_texture(
+        &mut self,
+        model: PieceModel,
+        texture: Handle<Image>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        for (color, material) in &self.map {
+            let base_color = materials
+                .get(material)
+                .map(|material| material.base_color)
+                .unwrap_or(Color::WHITE);
+
+            let textured = materials.add(StandardMaterial {
+                base_color,
+                base_color_texture: Some(texture.clone()),
+                ..Default::default()
+            });
+
+            self.textured.insert((*color, model), textured);
         }
     }
 
@@ -34,6 +92,13 @@ impl PieceMaterials {
         self.map.get(&color).expect("Piece material not found")
     }
 
+    /// Returns the material for a piece, textured if its kind has a texture.
+    pub fn get_for_piece(&self, color: PieceColor, model: PieceModel) -> &Handle<StandardMaterial> {
+        self.textured
+            .get(&(color, model))
+            .unwrap_or_else(|| self.get(color))
+    }
+
     /// Returns an iterator over the supported materials.
     pub fn materials(&self) -> impl Iterator<Item = (PieceColor, &Handle<StandardMaterial>)> {
         self.map.iter().map(|(color, material)| (*color, material))
@@ -1,6 +1,7 @@
 use crate::assets::meshes::piece::PieceMeshes;
 use bevy::{asset::Assets, mesh::Mesh};
 
+pub mod obj;
 pub mod piece;
 
 #[derive(Debug)]
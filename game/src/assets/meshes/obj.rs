@@ -0,0 +1,164 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, RenderAssetUsages, io::Reader},
+    mesh::{Mesh, PrimitiveTopology},
+    reflect::TypePath,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ObjError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid obj at line {0}")]
+    Invalid(usize),
+}
+
+/// Loads Wavefront OBJ files as a single mesh.
+///
+/// Only positions, texture coordinates, normals and polygonal faces are read.
+#[derive(Debug, Default, TypePath)]
+pub struct ObjLoader;
+
+impl AssetLoader for ObjLoader {
+    type Asset = Mesh;
+    type Settings = ();
+    type Error = ObjError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        parse_obj(&String::from_utf8_lossy(&bytes))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["obj"]
+    }
+}
+
+fn parse_obj(text: &str) -> Result<Mesh, ObjError> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+
+    // Vertices are emitted per face corner.
+    let mut out_positions = Vec::new();
+    let mut out_uvs = Vec::new();
+    let mut out_normals = Vec::new();
+
+    for (line_number, line) in text.lines().enumerate() {
+        let invalid = || ObjError::Invalid(line_number + 1);
+        let mut parts = line.split_whitespace();
+
+        let floats = |parts: std::str::SplitWhitespace, n: usize| -> Result<Vec<f32>, ObjError> {
+            let values: Vec<f32> = parts
+                .take(n)
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| invalid())?;
+
+            if values.len() < n {
+                Err(invalid())
+            } else {
+                Ok(values)
+            }
+        };
+
+        match parts.next() {
+            Some("v") => {
+                let v = floats(parts, 3)?;
+                positions.push([v[0], v[1], v[2]]);
+            }
+            Some("vt") => {
+                let v = floats(parts, 2)?;
+                // OBJ puts the origin at the bottom left.
+                uvs.push([v[0], 1.0 - v[1]]);
+            }
+            Some("vn") => {
+                let v = floats(parts, 3)?;
+                normals.push([v[0], v[1], v[2]]);
+            }
+            Some("f") => {
+                let corners: Vec<(usize, Option<usize>, Option<usize>)> = parts
+                    .map(|corner| parse_corner(corner, positions.len(), uvs.len(), normals.len()))
+                    .collect::<Option<_>>()
+                    .ok_or_else(invalid)?;
+
+                if corners.len() < 3 {
+                    return Err(invalid());
+                }
+
+                // Triangulate as a fan.
+                for i in 1..corners.len() - 1 {
+                    for (position, uv, normal) in [corners[0], corners[i], corners[i + 1]] {
+                        out_positions.push(positions[position]);
+                        out_uvs.push(uv.map_or([0.0, 0.0], |uv| uvs[uv]));
+                        out_normals.push(normal.map(|normal| normals[normal]));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let has_normals = out_normals.iter().all(Option::is_some);
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, out_positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, out_uvs);
+
+    if has_normals {
+        let normals: Vec<[f32; 3]> = out_normals.into_iter().flatten().collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    } else {
+        mesh.compute_flat_normals();
+    }
+
+    Ok(mesh)
+}
+
+/// Parses a face corner (`v`, `v/vt`, `v//vn` or `v/vt/vn`) into zero-based indices.
+///
+/// Negative indices count back from the last element read so far.
+fn parse_corner(
+    corner: &str,
+    positions: usize,
+    uvs: usize,
+    normals: usize,
+) -> Option<(usize, Option<usize>, Option<usize>)> {
+    let index = |value: &str, len: usize| -> Option<usize> {
+        let value: i64 = value.parse().ok()?;
+
+        let index = if value < 0 {
+            len as i64 + value
+        } else {
+            value - 1
+        };
+
+        usize::try_from(index).ok().filter(|index| *index < len)
+    };
+
+    let mut parts = corner.split('/');
+
+    let position = index(parts.next()?, positions)?;
+
+    let uv = match parts.next() {
+        Some("") | None => None,
+        Some(value) => Some(index(value, uvs)?),
+    };
+
+    let normal = match parts.next() {
+        Some("") | None => None,
+        Some(value) => Some(index(value, normals)?),
+    };
+
+    Some((position, uv, normal))
+}
@@ -4,20 +4,29 @@ use bevy::{
     mesh::Mesh,
     prelude::*,
 };
-use rulery::{CheckedGameRules, piece::PieceModel};
+use rulery::{
+    CheckedGameRules,
+    piece::{BuiltinModel, PieceModel},
+};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct PieceMeshes {
+    builtin: HashMap<BuiltinModel, (Handle<Mesh>, Transform)>,
+
+    /// Meshes of the piece kinds declared by the loaded rules.
     map: HashMap<PieceModel, (Handle<Mesh>, Transform)>,
+
+    /// Generic mesh for pieces whose model is hidden.
+    hidden: (Handle<Mesh>, Transform),
 }
 
 impl PieceMeshes {
     pub fn new(meshes: &mut Assets<Mesh>) -> Self {
         Self {
-            map: HashMap::from([
+            builtin: HashMap::from([
                 (
-                    PieceModel::Cube,
+                    BuiltinModel::Cube,
                     (
                         meshes.add(Cuboid::default()),
                         Transform {
@@ -28,7 +37,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Sphere,
+                    BuiltinModel::Sphere,
                     (
                         meshes.add(Sphere::default()),
                         Transform {
@@ -39,7 +48,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Cylinder,
+                    BuiltinModel::Cylinder,
                     (
                         meshes.add(Cylinder::default()),
                         Transform {
@@ -50,7 +59,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Cone,
+                    BuiltinModel::Cone,
                     (
                         meshes.add(Cone::default()),
                         Transform {
@@ -61,7 +70,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Capsule,
+                    BuiltinModel::Capsule,
                     (
                         meshes.add(Capsule3d::default()),
                         Transform {
@@ -72,7 +81,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Torus,
+                    BuiltinModel::Torus,
                     (
                         meshes.add(Torus::default()),
                         Transform {
@@ -84,7 +93,7 @@ impl PieceMeshes {
                     ),
                 ),
                 (
-                    PieceModel::Tetrahedron,
+                    BuiltinModel::Tetrahedron,
                     (
                         meshes.add(Tetrahedron::default()),
                         Transform {
@@ -95,10 +104,43 @@ impl PieceMeshes {
                     ),
                 ),
             ]),
+            map: HashMap::new(),
+            hidden: (
+                meshes.add(Cylinder::default()),
+                Transform {
+                    translation: Vec3::new(0.0, CheckedGameRules::tile_size() * 0.05, 0.0),
+                    scale: Vec3::new(
+                        CheckedGameRules::tile_size() * 0.6,
+                        CheckedGameRules::tile_size() * 0.1,
+                        CheckedGameRules::tile_size() * 0.6,
+                    ),
+                    ..default()
+                },
+            ),
         }
     }
 
+    /// Maps the piece kinds declared by the rules to the built-in meshes.
+    pub fn bind(&mut self, rules: &CheckedGameRules) {
+        self.map = rules
+            .pieces()
+            .map(|(model, _)| (model, self.builtin[&rules.model_mesh(model)].clone()))
+            .collect();
+    }
+
+    /// Replaces the mesh of a piece kind with one loaded from a file.
+    ///
+    /// Custom meshes are expected to stand on the origin and fit in a unit cube.
+    pub fn set_custom(&mut self, model: PieceModel, mesh: Handle<Mesh>) {
+        let transform = Transform::from_scale(Vec3::splat(CheckedGameRules::tile_size() * 0.5));
+        self.map.insert(model, (mesh, transform));
+    }
+
     pub fn get(&self, model: PieceModel) -> &(Handle<Mesh>, Transform) {
         self.map.get(&model).expect("No mesh found")
     }
+
+    pub fn hidden(&self) -> &(Handle<Mesh>, Transform) {
+        &self.hidden
+    }
 }
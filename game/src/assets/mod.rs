@@ -1,5 +1,6 @@
 use crate::assets::{materials::GameMaterials, meshes::GameMeshes};
 use bevy::{asset::Assets, ecs::resource::Resource, mesh::Mesh, pbr::StandardMaterial};
+use rulery::CheckedGameRules;
 
 pub mod materials;
 pub mod meshes;
@@ -17,4 +18,10 @@ impl GameAssets {
             meshes: GameMeshes::new(meshes),
         }
     }
+
+    /// Binds piece meshes and materials to the kinds and players declared by the rules.
+    pub fn bind(&mut self, rules: &CheckedGameRules, materials: &mut Assets<StandardMaterial>) {
+        self.meshes.piece.bind(rules);
+        self.materials.piece.bind(rules, materials);
+    }
 }
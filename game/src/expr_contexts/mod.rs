@@ -1,20 +1,31 @@
 use crate::{
     GameError,
-    states::playing::session::{PlacedPieceIndex, turn::TurnController},
+    states::playing::session::{
+        PlacedPieceIndex, clock::Clocks, counters::TurnCounters, random::RandomCalls, team::Teams,
+        turn::TurnController, zobrist::PositionHistory,
+    },
 };
 use rulery::{
+    board::BoardWrap,
     piece::{PieceColor, PieceModel},
     pos::Pos,
     rect::Rect,
 };
 
+pub mod capture;
 pub mod game_over;
 pub mod movement;
 pub mod placement;
+pub mod turn_order;
+pub mod visibility;
 pub mod win_or_lose;
 
-fn query_pos_occupied(index: &PlacedPieceIndex, pos: Pos) -> Result<bool, GameError> {
-    Ok(index.get(&pos).is_some())
+fn query_pos_occupied(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+) -> Result<bool, GameError> {
+    Ok(index.is_occupied(wrap.normalize(pos)))
 }
 
 fn query_has_last_action(last_action: &Option<Pos>) -> Result<bool, GameError> {
@@ -29,6 +40,51 @@ fn query_round_number(turn: &TurnController) -> Result<i64, GameError> {
     Ok(turn.round_number())
 }
 
+fn query_random(
+    turn: &TurnController,
+    calls: &RandomCalls,
+    min: i64,
+    max: i64,
+) -> Result<i64, GameError> {
+    turn.random(calls.next(), min, max)
+}
+
+fn query_dice_value(turn: &TurnController) -> Result<i64, GameError> {
+    turn.dice_value().ok_or(GameError::NoDice)
+}
+
+fn query_turns_since_capture(
+    counters: &TurnCounters,
+    turn: &TurnController,
+) -> Result<i64, GameError> {
+    Ok(counters.turns_since_capture(turn.turn_number()))
+}
+
+fn query_turns_since_placement(
+    counters: &TurnCounters,
+    turn: &TurnController,
+) -> Result<i64, GameError> {
+    Ok(counters.turns_since_placement(turn.turn_number()))
+}
+
+fn query_turns_since_move_of(
+    counters: &TurnCounters,
+    turn: &TurnController,
+    model: PieceModel,
+) -> Result<i64, GameError> {
+    Ok(counters.turns_since_move_of(model, turn.turn_number()))
+}
+
+fn query_repetition_count(history: &PositionHistory) -> Result<i64, GameError> {
+    Ok(history.repetition_count())
+}
+
+fn query_remaining_millis(clocks: &Clocks, color: PieceColor) -> Result<i64, GameError> {
+    let remaining = clocks.remaining(color).ok_or(GameError::NoTimeControl)?;
+
+    Ok(i64::try_from(remaining.as_millis()).unwrap_or(i64::MAX))
+}
+
 fn query_last_action_row(last_action: &Option<Pos>) -> Result<i64, GameError> {
     match last_action {
         Some(pos) => Ok(pos.row()),
@@ -43,11 +99,25 @@ fn query_last_action_col(last_action: &Option<Pos>) -> Result<i64, GameError> {
     }
 }
 
-fn query_count_in_rect(rect: Rect, index: &PlacedPieceIndex) -> Result<i64, GameError> {
+fn query_count_in_rect(
+    rect: Rect,
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+) -> Result<i64, GameError> {
     Ok(index
-        .keys()
-        .copied()
-        .filter(|pos| rect.contains(*pos))
+        .tops()
+        .filter(|placed| rect.contains_wrapped(placed.pos(), wrap))
+        .count() as i64)
+}
+
+fn query_count_pieces_in_rect(
+    rect: Rect,
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+) -> Result<i64, GameError> {
+    Ok(index
+        .iter()
+        .filter(|placed| rect.contains_wrapped(placed.pos(), wrap))
         .count() as i64)
 }
 
@@ -55,28 +125,125 @@ fn query_count_piece_in_rect(
     piece: (PieceModel, PieceColor),
     rect: Rect,
     index: &PlacedPieceIndex,
+    wrap: BoardWrap,
 ) -> Result<i64, GameError> {
     let (want_model, want_color) = piece;
 
     Ok(index
-        .iter()
-        .filter(|&(pos, _)| rect.contains(*pos))
-        .filter(|&(_, placed)| placed.model() == want_model && placed.color() == want_color)
+        .tops()
+        .filter(|placed| rect.contains_wrapped(placed.pos(), wrap))
+        .filter(|placed| placed.model() == want_model && placed.color() == want_color)
         .count() as i64)
 }
 
-fn query_model_at_pos(index: &PlacedPieceIndex, pos: Pos) -> Result<PieceModel, GameError> {
-    let Some(placed) = index.get(&pos) else {
+fn query_same_team(teams: &Teams, a: PieceColor, b: PieceColor) -> Result<bool, GameError> {
+    Ok(teams.same_team(a, b))
+}
+
+fn query_model_at_pos(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+) -> Result<PieceModel, GameError> {
+    let pos = wrap.normalize(pos);
+
+    let Some(placed) = index.get(pos) else {
         return Err(GameError::NoPieceAtPos(pos));
     };
 
     Ok(placed.model())
 }
 
-fn query_color_at_pos(index: &PlacedPieceIndex, pos: Pos) -> Result<PieceColor, GameError> {
-    let Some(placed) = index.get(&pos) else {
+fn query_color_at_pos(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+) -> Result<PieceColor, GameError> {
+    let pos = wrap.normalize(pos);
+
+    let Some(placed) = index.get(pos) else {
         return Err(GameError::NoPieceAtPos(pos));
     };
 
     Ok(placed.color())
 }
+
+fn query_stack_height(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+) -> Result<i64, GameError> {
+    Ok(index.height(wrap.normalize(pos)) as i64)
+}
+
+fn query_model_at_depth(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+    depth: i64,
+) -> Result<PieceModel, GameError> {
+    let pos = wrap.normalize(pos);
+
+    let Some(placed) = index.get_at_depth(pos, depth) else {
+        return Err(GameError::NoPieceAtDepth(pos, depth));
+    };
+
+    Ok(placed.model())
+}
+
+fn query_color_at_depth(
+    index: &PlacedPieceIndex,
+    wrap: BoardWrap,
+    pos: Pos,
+    depth: i64,
+) -> Result<PieceColor, GameError> {
+    let pos = wrap.normalize(pos);
+
+    let Some(placed) = index.get_at_depth(pos, depth) else {
+        return Err(GameError::NoPieceAtDepth(pos, depth));
+    };
+
+    Ok(placed.color())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::playing::session::tests::placed;
+    use rulery::clock::TimeControl;
+
+    #[test]
+    fn rect_counts_tiles_or_pieces() {
+        let mut index = PlacedPieceIndex::new();
+
+        index.push(placed("Stone", "White", 0, 0));
+        index.push(placed("Stone", "Black", 0, 0));
+        index.push(placed("Stone", "White", 1, 1));
+
+        let rect = Rect::new(Pos::new(0, 0), Pos::new(1, 1));
+        let wrap = BoardWrap::default();
+        let white = (PieceModel::new("Stone"), PieceColor::new("White"));
+
+        assert_eq!(query_count_in_rect(rect, &index, wrap).unwrap(), 2);
+        assert_eq!(query_count_pieces_in_rect(rect, &index, wrap).unwrap(), 3);
+
+        // Only the top piece of a stack counts for its model and color.
+        assert_eq!(
+            query_count_piece_in_rect(white, rect, &index, wrap).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn remaining_millis_reads_the_clocks() {
+        let white = PieceColor::new("White");
+        let control = TimeControl::new(90_000, 0, None);
+        let clocks = Clocks::with_time_control(&control, [white]);
+
+        assert_eq!(query_remaining_millis(&clocks, white).unwrap(), 90_000);
+        assert!(matches!(
+            query_remaining_millis(&Clocks::default(), white),
+            Err(GameError::NoTimeControl)
+        ));
+    }
+}
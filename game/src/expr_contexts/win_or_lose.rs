@@ -1,11 +1,14 @@
 use crate::{
     GameError,
     expr_contexts::{
-        query_color_at_pos, query_count_in_rect, query_count_piece_in_rect, query_has_last_action,
-        query_last_action_col, query_last_action_row, query_model_at_pos, query_pos_occupied,
-        query_round_number, query_turn_number,
+        query_color_at_depth, query_color_at_pos, query_count_in_rect, query_count_piece_in_rect,
+        query_count_pieces_in_rect, query_dice_value, query_has_last_action, query_last_action_col,
+        query_last_action_row, query_model_at_depth, query_model_at_pos, query_pos_occupied,
+        query_random, query_remaining_millis, query_repetition_count, query_round_number,
+        query_same_team, query_stack_height, query_turn_number, query_turns_since_capture,
+        query_turns_since_move_of, query_turns_since_placement,
     },
-    states::playing::session::{PlacedPieceIndex, turn::TurnController},
+    states::playing::session::{GameSession, random::RandomCalls},
 };
 use rulery::{
     expr::Context,
@@ -15,41 +18,40 @@ use rulery::{
 };
 
 #[derive(Debug)]
-pub struct WinOrLoseContext<'t, 'l, 'i> {
-    pub turn: &'t TurnController,
-    pub last_action: &'l Option<Pos>,
-    pub placed_piece_index: &'i PlacedPieceIndex,
+pub struct WinOrLoseContext<'s> {
+    pub session: &'s GameSession,
+    pub random_calls: RandomCalls,
 }
 
-impl Context for WinOrLoseContext<'_, '_, '_> {
+impl Context for WinOrLoseContext<'_> {
     type Error = GameError;
 
     fn pos_occupied(&self, pos: Pos) -> Result<bool, Self::Error> {
-        query_pos_occupied(&self.placed_piece_index, pos)
+        query_pos_occupied(&self.session.placed_pieces, self.session.wrap, pos)
     }
 
     fn has_last_action(&self) -> Result<bool, Self::Error> {
-        query_has_last_action(self.last_action)
+        query_has_last_action(&self.session.last_action)
     }
 
     fn turn_number(&self) -> Result<i64, Self::Error> {
-        query_turn_number(&self.turn)
+        query_turn_number(&self.session.turn)
     }
 
     fn round_number(&self) -> Result<i64, Self::Error> {
-        query_round_number(&self.turn)
+        query_round_number(&self.session.turn)
     }
 
     fn last_action_row(&self) -> Result<i64, Self::Error> {
-        query_last_action_row(self.last_action)
+        query_last_action_row(&self.session.last_action)
     }
 
     fn last_action_col(&self) -> Result<i64, Self::Error> {
-        query_last_action_col(self.last_action)
+        query_last_action_col(&self.session.last_action)
     }
 
     fn count_in_rect(&self, rect: Rect) -> Result<i64, Self::Error> {
-        query_count_in_rect(rect, &self.placed_piece_index)
+        query_count_in_rect(rect, &self.session.placed_pieces, self.session.wrap)
     }
 
     fn count_piece_in_rect(
@@ -57,14 +59,62 @@ impl Context for WinOrLoseContext<'_, '_, '_> {
         piece: (PieceModel, PieceColor),
         rect: Rect,
     ) -> Result<i64, Self::Error> {
-        query_count_piece_in_rect(piece, rect, &self.placed_piece_index)
+        query_count_piece_in_rect(piece, rect, &self.session.placed_pieces, self.session.wrap)
+    }
+
+    fn count_pieces_in_rect(&self, rect: Rect) -> Result<i64, Self::Error> {
+        query_count_pieces_in_rect(rect, &self.session.placed_pieces, self.session.wrap)
+    }
+
+    fn random(&self, min: i64, max: i64) -> Result<i64, Self::Error> {
+        query_random(&self.session.turn, &self.random_calls, min, max)
+    }
+
+    fn dice_value(&self) -> Result<i64, Self::Error> {
+        query_dice_value(&self.session.turn)
+    }
+
+    fn turns_since_capture(&self) -> Result<i64, Self::Error> {
+        query_turns_since_capture(&self.session.counters, &self.session.turn)
+    }
+
+    fn turns_since_placement(&self) -> Result<i64, Self::Error> {
+        query_turns_since_placement(&self.session.counters, &self.session.turn)
+    }
+
+    fn turns_since_move_of(&self, model: PieceModel) -> Result<i64, Self::Error> {
+        query_turns_since_move_of(&self.session.counters, &self.session.turn, model)
+    }
+
+    fn repetition_count(&self) -> Result<i64, Self::Error> {
+        query_repetition_count(&self.session.history)
+    }
+
+    fn remaining_millis(&self, color: PieceColor) -> Result<i64, Self::Error> {
+        query_remaining_millis(&self.session.clocks, color)
+    }
+
+    fn same_team(&self, a: PieceColor, b: PieceColor) -> Result<bool, Self::Error> {
+        query_same_team(&self.session.teams, a, b)
     }
 
     fn model_at_pos(&self, pos: Pos) -> Result<PieceModel, Self::Error> {
-        query_model_at_pos(&self.placed_piece_index, pos)
+        query_model_at_pos(&self.session.placed_pieces, self.session.wrap, pos)
     }
 
     fn color_at_pos(&self, pos: Pos) -> Result<PieceColor, Self::Error> {
-        query_color_at_pos(&self.placed_piece_index, pos)
+        query_color_at_pos(&self.session.placed_pieces, self.session.wrap, pos)
+    }
+
+    fn stack_height(&self, pos: Pos) -> Result<i64, Self::Error> {
+        query_stack_height(&self.session.placed_pieces, self.session.wrap, pos)
+    }
+
+    fn model_at_depth(&self, pos: Pos, depth: i64) -> Result<PieceModel, Self::Error> {
+        query_model_at_depth(&self.session.placed_pieces, self.session.wrap, pos, depth)
+    }
+
+    fn color_at_depth(&self, pos: Pos, depth: i64) -> Result<PieceColor, Self::Error> {
+        query_color_at_depth(&self.session.placed_pieces, self.session.wrap, pos, depth)
     }
 }
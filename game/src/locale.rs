@@ -0,0 +1,273 @@
+use crate::GameError;
+use bevy::prelude::*;
+use bevy_egui::{
+    EguiContext, PrimaryEguiContext,
+    egui::{
+        FontData, FontFamily,
+        epaint::text::{FontInsert, FontPriority, InsertFontFamily},
+    },
+};
+use rulery::player::PlayerState;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// Languages of the game's own texts, also used to pick texts in the rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    Chinese,
+    Japanese,
+}
+
+impl Locale {
+    /// Returns the locale code, e.g. `zh`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Chinese => "zh",
+            Locale::Japanese => "ja",
+        }
+    }
+
+    /// Returns the game's texts in this language.
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Locale::English => &ENGLISH,
+            Locale::Chinese => &CHINESE,
+            Locale::Japanese => &JAPANESE,
+        }
+    }
+}
+
+/// The game's own texts in one language.
+///
+/// Templates name their arguments in braces, e.g. `{player}`, see [`fill`].
+pub struct Strings {
+    pub new_game: &'static str,
+    pub loaded_rules: &'static str,
+    pub ready: &'static str,
+    pub version: &'static str,
+    pub authors: &'static str,
+    pub license: &'static str,
+    pub recommended_players: &'static str,
+    pub tags: &'static str,
+    pub rules: &'static str,
+    pub in_stock: &'static str,
+    pub in_stock_hint: &'static str,
+    pub in_hand: &'static str,
+    pub captured: &'static str,
+    pub captured_hint: &'static str,
+    pub score: &'static str,
+    pub score_hint: &'static str,
+    pub turn: &'static str,
+    pub actions_left: &'static str,
+    pub rolled: &'static str,
+    pub game_over: &'static str,
+    pub rank: &'static str,
+    pub player: &'static str,
+    pub result: &'static str,
+    pub promotion: &'static str,
+    pub choose_promotion: &'static str,
+    pub seed: &'static str,
+    pub dice_rolls: &'static str,
+    pub byo_yomi: &'static str,
+    pub position: &'static str,
+    pub back_to_menu: &'static str,
+    pub active: &'static str,
+    pub won: &'static str,
+    pub lost: &'static str,
+    pub draw: &'static str,
+
+    /// Separates the parts of a message.
+    pub separator: &'static str,
+
+    /// Separates the clocks of the players.
+    pub clock_separator: &'static str,
+}
+
+impl Strings {
+    /// Returns the name of a player state.
+    pub fn state(&self, state: PlayerState) -> &'static str {
+        match state {
+            PlayerState::Active => self.active,
+            PlayerState::Won => self.won,
+            PlayerState::Lost => self.lost,
+            PlayerState::Draw => self.draw,
+        }
+    }
+}
+
+/// Replaces the named arguments of a template.
+pub fn fill(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+/// The font set in the settings, kept to add it to every egui context.
+#[derive(Resource)]
+pub struct UiFont(Vec<u8>);
+
+impl UiFont {
+    /// Loads a font file and makes it the default font of texts outside egui.
+    pub fn load(path: &Path, fonts: &mut Assets<Font>) -> Result<Self, GameError> {
+        let bytes = fs::read(path)?;
+        let font = Font::try_from_bytes(bytes.clone())
+            .map_err(|err| GameError::Font(format!("{}: {err}", path.display())))?;
+
+        fonts
+            .insert(AssetId::default(), font)
+            .map_err(|err| GameError::Font(err.to_string()))?;
+
+        Ok(Self(bytes))
+    }
+}
+
+/// A system that adds the font set in the settings to new egui contexts.
+///
+/// The font is preferred over egui's own proportional fonts, which keep covering the glyphs
+/// it lacks, and backs up the monospace fonts.
+pub fn add_egui_font(
+    font: Option<Res<UiFont>>,
+    mut contexts: Query<&mut EguiContext, Added<PrimaryEguiContext>>,
+) {
+    let Some(font) = font else {
+        return;
+    };
+
+    for mut context in &mut contexts {
+        context.get_mut().add_font(FontInsert::new(
+            "settings_font",
+            FontData::from_owned(font.0.clone()),
+            vec![
+                InsertFontFamily {
+                    family: FontFamily::Proportional,
+                    priority: FontPriority::Highest,
+                },
+                InsertFontFamily {
+                    family: FontFamily::Monospace,
+                    priority: FontPriority::Lowest,
+                },
+            ],
+        ));
+    }
+}
+
+const ENGLISH: Strings = Strings {
+    new_game: "New Game",
+    loaded_rules: "Loaded Rules: {name}",
+    ready: "Ready!",
+    version: "Version {version}",
+    authors: "By {authors}",
+    license: "License: {license}",
+    recommended_players: "Players: {counts}",
+    tags: "Tags: {tags}",
+    rules: "Rules",
+    in_stock: "In Stock",
+    in_stock_hint: "Number of your pieces available for placement",
+    in_hand: "{name} × {count} (In Hand)",
+    captured: "Captured",
+    captured_hint: "Number of your pieces that have been captured",
+    score: "Score",
+    score_hint: "Current score of every player",
+    turn: "{player}'s Turn — Turn {turn}, Round {round}",
+    actions_left: "{left} of {total} Actions Left",
+    rolled: "Rolled {value}",
+    game_over: "Game Over: {results}",
+    rank: "Rank",
+    player: "Player",
+    result: "Result",
+    promotion: "Promotion",
+    choose_promotion: "Choose a piece to promote to",
+    seed: "Seed {seed}",
+    dice_rolls: "Dice Rolls: {rolls}",
+    byo_yomi: "{clock} ({periods}×{secs}s)",
+    position: "Position {hash}",
+    back_to_menu: "Back to Menu",
+    active: "Active",
+    won: "Won",
+    lost: "Lost",
+    draw: "Draw",
+    separator: ", ",
+    clock_separator: " · ",
+};
+
+const CHINESE: Strings = Strings {
+    new_game: "新游戏",
+    loaded_rules: "已加载规则：{name}",
+    ready: "准备好了！",
+    version: "版本 {version}",
+    authors: "作者：{authors}",
+    license: "许可证：{license}",
+    recommended_players: "推荐人数：{counts}",
+    tags: "标签：{tags}",
+    rules: "规则",
+    in_stock: "库存",
+    in_stock_hint: "可放置的棋子数量",
+    in_hand: "{name} × {count}（手中）",
+    captured: "被吃",
+    captured_hint: "已被吃掉的棋子数量",
+    score: "得分",
+    score_hint: "每位玩家的当前得分",
+    turn: "{player}的回合 — 第 {turn} 手，第 {round} 轮",
+    actions_left: "剩余 {left}/{total} 次行动",
+    rolled: "掷出 {value}",
+    game_over: "游戏结束：{results}",
+    rank: "名次",
+    player: "玩家",
+    result: "结果",
+    promotion: "升变",
+    choose_promotion: "选择要升变成的棋子",
+    seed: "种子 {seed}",
+    dice_rolls: "骰子点数：{rolls}",
+    byo_yomi: "{clock}（读秒 {periods}×{secs}秒）",
+    position: "局面 {hash}",
+    back_to_menu: "返回菜单",
+    active: "进行中",
+    won: "胜",
+    lost: "负",
+    draw: "和",
+    separator: "，",
+    clock_separator: " · ",
+};
+
+const JAPANESE: Strings = Strings {
+    new_game: "新しいゲーム",
+    loaded_rules: "読み込んだルール：{name}",
+    ready: "準備完了！",
+    version: "バージョン {version}",
+    authors: "作者：{authors}",
+    license: "ライセンス：{license}",
+    recommended_players: "推奨人数：{counts}",
+    tags: "タグ：{tags}",
+    rules: "ルール",
+    in_stock: "在庫",
+    in_stock_hint: "配置できる駒の数",
+    in_hand: "{name} × {count}（持ち駒）",
+    captured: "取られた駒",
+    captured_hint: "取られた駒の数",
+    score: "得点",
+    score_hint: "各プレイヤーの現在の得点",
+    turn: "{player}の番 — 第 {turn} 手、第 {round} ラウンド",
+    actions_left: "残り {left}/{total} 手",
+    rolled: "出目 {value}",
+    game_over: "ゲーム終了：{results}",
+    rank: "順位",
+    player: "プレイヤー",
+    result: "結果",
+    promotion: "成り",
+    choose_promotion: "成る駒を選んでください",
+    seed: "シード {seed}",
+    dice_rolls: "サイコロの出目：{rolls}",
+    byo_yomi: "{clock}（秒読み {periods}×{secs}秒）",
+    position: "局面 {hash}",
+    back_to_menu: "メニューに戻る",
+    active: "対局中",
+    won: "勝ち",
+    lost: "負け",
+    draw: "引き分け",
+    separator: "、",
+    clock_separator: " · ",
+};
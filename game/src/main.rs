@@ -1,13 +1,14 @@
-use crate::states::AppStatePlugin;
-use bevy::prelude::*;
+use crate::{assets::meshes::obj::ObjLoader, states::AppStatePlugin};
+use bevy::{asset::UnapprovedPathMode, prelude::*};
 use bevy_egui::EguiPlugin;
 use bevy_tweening::TweeningPlugin;
 use ron::de::SpannedError;
-use rulery::{RulesError, pos::Pos};
+use rulery::{RulesError, piece::PieceColor, pos::Pos};
 use thiserror::Error;
 
 mod assets;
 mod expr_contexts;
+mod locale;
 mod settings;
 mod states;
 
@@ -21,6 +22,20 @@ pub enum GameError {
     DuplicatePiece(Pos),
     #[error("no piece at position: {0}")]
     NoPieceAtPos(Pos),
+    #[error("no piece at depth {1} of position: {0}")]
+    NoPieceAtDepth(Pos, i64),
+    #[error("no such team: {0}")]
+    NoSuchTeam(String),
+    #[error("player has no score: {0}")]
+    NoScore(PieceColor),
+    #[error("no time control")]
+    NoTimeControl,
+    #[error("no dice rolled")]
+    NoDice,
+    #[error("invalid random range: {0}..={1}")]
+    InvalidRandomRange(i64, i64),
+    #[error("failed to load font: {0}")]
+    Font(String),
     #[error("rules error: {0}")]
     Rules(#[from] RulesError),
     #[error("config format error: {0}")]
@@ -41,9 +56,22 @@ fn new_window_plugin() -> WindowPlugin {
     }
 }
 
+fn new_asset_plugin() -> AssetPlugin {
+    // Rules reference assets next to the rules file, outside the asset folder.
+    AssetPlugin {
+        unapproved_path_mode: UnapprovedPathMode::Deny,
+        ..default()
+    }
+}
+
 fn main() {
     App::new()
-        .add_plugins(DefaultPlugins.set(new_window_plugin()))
+        .add_plugins(
+            DefaultPlugins
+                .set(new_window_plugin())
+                .set(new_asset_plugin()),
+        )
+        .init_asset_loader::<ObjLoader>()
         .add_plugins(MeshPickingPlugin)
         .add_plugins(TweeningPlugin)
         .add_plugins(EguiPlugin::default())
@@ -1,4 +1,4 @@
-use crate::GameError;
+use crate::{GameError, locale::Locale};
 use bevy::ecs::resource::Resource;
 use ron::de::from_reader;
 use serde::{Deserialize, Serialize};
@@ -11,6 +11,20 @@ use std::{
 #[derive(Debug, Serialize, Deserialize, Resource)]
 pub struct Settings {
     pub rules_path: PathBuf,
+
+    /// Fixed seed for dice and random draws, for reproducible games.
+    ///
+    /// A seed is taken from the system time if unset.
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// Language of the game's texts and of the rules' texts, English by default.
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Font to show texts with, needed for scripts the default font lacks, e.g. Chinese.
+    #[serde(default)]
+    pub font: Option<PathBuf>,
 }
 
 impl Settings {
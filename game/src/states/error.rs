@@ -1,4 +1,4 @@
-use crate::{GameError, states::AppState};
+use crate::{GameError, settings::Settings, states::AppState};
 use bevy::prelude::*;
 
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
@@ -21,7 +21,9 @@ pub struct CurrentError(pub GameError);
 #[derive(Component)]
 struct ErrorMarker;
 
-fn on_enter(mut commands: Commands, error: Res<CurrentError>) {
+fn on_enter(mut commands: Commands, error: Res<CurrentError>, settings: Res<Settings>) {
+    let strings = settings.locale.strings();
+
     // camera
     commands.spawn((Camera2d, ErrorMarker));
 
@@ -42,7 +44,7 @@ fn on_enter(mut commands: Commands, error: Res<CurrentError>) {
             parent.spawn(spacer());
             parent.spawn(label(error.0.to_string()));
             parent.spawn(spacer());
-            parent.spawn(button());
+            parent.spawn(button(strings.back_to_menu));
             parent.spawn(spacer());
         });
 }
@@ -112,7 +114,7 @@ fn spacer() -> impl Bundle + 'static {
     }
 }
 
-fn button() -> impl Bundle + 'static {
+fn button(text: impl Into<String>) -> impl Bundle + 'static {
     (
         Button,
         Node {
@@ -127,7 +129,7 @@ fn button() -> impl Bundle + 'static {
         BorderColor::all(Color::BLACK),
         BackgroundColor(NORMAL_BUTTON),
         children![(
-            Text::new("Back to Menu"),
+            Text::new(text),
             TextFont {
                 font_size: 28.0,
                 ..default()
@@ -1,9 +1,10 @@
 use crate::{
+    locale::{Strings, fill},
     settings::Settings,
     states::{AppState, error::CurrentError},
 };
 use bevy::prelude::*;
-use rulery::{CheckedGameRules, UncheckedGameRules};
+use rulery::{CheckedGameRules, UncheckedGameRules, metadata::Metadata};
 use std::ops::Deref;
 
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
@@ -39,10 +40,14 @@ fn on_enter(
     mut next_state: ResMut<NextState<AppState>>,
     settings: Res<Settings>,
 ) {
-    // Load rules from settings and check it
-    let checked = match UncheckedGameRules::load(settings.rules_path.as_path())
-        .and_then(|unchecked| unchecked.check())
-    {
+    // Load rules from settings and check it, texts of the rules follow the game's language
+    let loaded =
+        UncheckedGameRules::load(settings.rules_path.as_path()).and_then(|mut unchecked| {
+            unchecked.set_locale(settings.locale.code());
+            unchecked.check()
+        });
+
+    let checked = match loaded {
         Ok(rules) => rules,
         Err(err) => {
             commands.insert_resource(CurrentError(err.into()));
@@ -51,6 +56,8 @@ fn on_enter(
         }
     };
 
+    let strings = settings.locale.strings();
+
     // camera
     commands.spawn((Camera2d, GameSetupMarker));
 
@@ -69,9 +76,21 @@ fn on_enter(
         ))
         .with_children(|parent| {
             parent.spawn(spacer());
-            parent.spawn(label(format!("Loaded Rules: {}", checked.name()), 50.0));
+            parent.spawn(label(
+                fill(strings.loaded_rules, &[("name", &checked.name())]),
+                50.0,
+            ));
+
+            if let Some(description) = checked.description() {
+                parent.spawn(paragraph(description, 26.0));
+            }
+
+            for line in metadata_lines(checked.metadata(), strings) {
+                parent.spawn(label(line, 22.0));
+            }
+
             parent.spawn(spacer());
-            parent.spawn(button("Ready!"));
+            parent.spawn(button(strings.ready));
             parent.spawn(spacer());
         });
 
@@ -127,6 +146,60 @@ fn label(text: impl Into<String>, font_size: f32) -> impl Bundle + 'static {
     )
 }
 
+/// Lines describing the rules, e.g. `By Alice, Bob` or `Players: 2, 4`.
+fn metadata_lines(metadata: &Metadata, strings: &Strings) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(version) = metadata.version() {
+        lines.push(fill(strings.version, &[("version", &version)]));
+    }
+
+    if !metadata.authors().is_empty() {
+        let authors = metadata.authors().join(strings.separator);
+        lines.push(fill(strings.authors, &[("authors", &authors)]));
+    }
+
+    if let Some(license) = metadata.license() {
+        lines.push(fill(strings.license, &[("license", &license)]));
+    }
+
+    if !metadata.recommended_players().is_empty() {
+        let counts: Vec<String> = metadata
+            .recommended_players()
+            .iter()
+            .map(u32::to_string)
+            .collect();
+
+        let counts = counts.join(strings.separator);
+        lines.push(fill(strings.recommended_players, &[("counts", &counts)]));
+    }
+
+    if !metadata.tags().is_empty() {
+        let tags = metadata.tags().join(strings.separator);
+        lines.push(fill(strings.tags, &[("tags", &tags)]));
+    }
+
+    lines
+}
+
+/// A label wrapped to a readable width.
+fn paragraph(text: impl Into<String>, font_size: f32) -> impl Bundle + 'static {
+    (
+        Node {
+            max_width: Val::Percent(60.0),
+            margin: UiRect::vertical(Val::Px(12.0)),
+            ..default()
+        },
+        Text::new(text),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        TextColor(Color::srgb(0.75, 0.75, 0.75)),
+    )
+}
+
 fn spacer() -> impl Bundle + 'static {
     Node {
         flex_grow: 1.0,
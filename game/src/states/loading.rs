@@ -1,5 +1,14 @@
-use crate::states::AppState;
-use bevy::prelude::*;
+use crate::{
+    assets::GameAssets,
+    states::{AppState, game_setup::LoadedRules},
+};
+use bevy::{
+    asset::{LoadState, UntypedAssetId},
+    gltf::GltfAssetLabel,
+    prelude::*,
+};
+use rulery::piece::PieceModel;
+use std::path::{Path, PathBuf};
 
 pub struct LoadingPlugin;
 
@@ -11,21 +20,146 @@ impl Plugin for LoadingPlugin {
     }
 }
 
-fn on_enter() {
-    // no-op
+/// Piece assets declared by the rules that are still loading.
+#[derive(Resource, Default)]
+struct PendingPieceAssets {
+    meshes: Vec<(PieceModel, Handle<Mesh>)>,
+    textures: Vec<(PieceModel, Handle<Image>)>,
 }
 
-fn update(mut next_state: ResMut<NextState<AppState>>) {
+fn on_enter(
+    mut commands: Commands,
+    mut assets: ResMut<GameAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    rules: Res<LoadedRules>,
+) {
+    // Bind piece assets to the declared kinds and players
+    assets.bind(&rules, &mut materials);
+
+    let mut pending = PendingPieceAssets::default();
+
+    // Missing files keep the built-in meshes and plain materials.
+    for (model, _) in rules.pieces() {
+        if let Some(path) = rules.model_mesh_file(model).and_then(resolve) {
+            pending
+                .meshes
+                .push((model, load_mesh(&asset_server, &path)));
+        }
+
+        if let Some(path) = rules.model_texture_file(model).and_then(resolve) {
+            // Files next to the rules are outside the asset folder, hence the override.
+            pending
+                .textures
+                .push((model, asset_server.load_override(path)));
+        }
+    }
+
+    commands.insert_resource(pending);
+}
+
+/// Resolves a file declared by the rules to an absolute path, or logs why it cannot be used.
+///
+/// Declared paths are relative to the working directory, unlike the asset server's root.
+fn resolve(path: PathBuf) -> Option<PathBuf> {
+    path.canonicalize()
+        .inspect_err(|err| warn!("{}: {err}, keeping the built-in asset", path.display()))
+        .ok()
+}
+
+fn update(
+    mut next_state: ResMut<NextState<AppState>>,
+    mut assets: ResMut<GameAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    pending: Res<PendingPieceAssets>,
+) {
     if let NextState::Pending(_) = *next_state {
         return;
     }
 
-    // Nothing to do now
+    // Wait until every asset has loaded or failed.
+    let ids = pending
+        .meshes
+        .iter()
+        .map(|(_, handle)| handle.id().untyped())
+        .chain(
+            pending
+                .textures
+                .iter()
+                .map(|(_, handle)| handle.id().untyped()),
+        );
+
+    for id in ids {
+        if matches!(
+            asset_server.load_state(id),
+            LoadState::NotLoaded | LoadState::Loading
+        ) {
+            return;
+        }
+    }
+
+    // Files that failed to load keep the built-in meshes and plain materials.
+    let is_loaded = |id: UntypedAssetId| match asset_server.load_state(id) {
+        LoadState::Failed(err) => {
+            warn!("{err}, keeping the built-in asset");
+            false
+        }
+        _ => true,
+    };
+
+    for (model, mesh) in &pending.meshes {
+        if is_loaded(mesh.id().untyped()) {
+            assets.meshes.piece.set_custom(*model, mesh.clone());
+        }
+    }
+
+    for (model, texture) in &pending.textures {
+        if is_loaded(texture.id().untyped()) {
+            assets
+                .materials
+                .piece
+                .set_texture(*model, texture.clone(), &mut materials);
+        }
+    }
 
     // Switch to the `Playing` state.
     next_state.set(AppState::Playing);
 }
 
-fn on_exit() {
-    // no-op
+fn on_exit(mut commands: Commands) {
+    commands.remove_resource::<PendingPieceAssets>();
+}
+
+/// Loads a mesh from a glTF or OBJ file.
+fn load_mesh(asset_server: &AssetServer, path: &Path) -> Handle<Mesh> {
+    let is_gltf = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gltf") || ext.eq_ignore_ascii_case("glb"));
+
+    if is_gltf {
+        // The first primitive of the first mesh.
+        asset_server.load_override(
+            GltfAssetLabel::Primitive {
+                mesh: 0,
+                primitive: 0,
+            }
+            .from_asset(path.to_path_buf()),
+        )
+    } else {
+        asset_server.load_override(path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_files_keep_the_built_in_assets() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+        assert!(resolve(dir.join("Cargo.toml")).is_some());
+        assert_eq!(resolve(dir.join("no-such-mesh.glb")), None);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::states::AppState;
+use crate::{settings::Settings, states::AppState};
 use bevy::prelude::*;
 
 const BUTTON_NORMAL: Color = Color::srgba(0.15, 0.15, 0.15, 0.6);
@@ -56,7 +56,7 @@ fn update(
     }
 }
 
-fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>, settings: Res<Settings>) {
     // camera
     commands.spawn((Camera2d, MenuMarker));
 
@@ -87,7 +87,7 @@ fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
                     parent.spawn(spacer());
                     parent.spawn(logo(asset_server.load(LOGO_PATH)));
                     parent.spawn(spacer());
-                    parent.spawn(button("New Game"));
+                    parent.spawn(button(settings.locale.strings().new_game));
                     parent.spawn(spacer());
                 });
 
@@ -8,6 +8,7 @@ use crate::{
 };
 use bevy::prelude::*;
 use rulery::{CheckedGameRules, pos::Pos};
+use std::f32::consts::FRAC_PI_2;
 
 /// Spawns the board.
 pub fn spawn_board(
@@ -53,11 +54,21 @@ pub fn spawn_board(
 
     commands.entity(board_root).add_child(tiles_transform);
 
-    let tile_mesh = meshes.add(Cuboid::new(
-        CheckedGameRules::tile_size(),
-        CheckedGameRules::tile_height(),
-        CheckedGameRules::tile_size(),
-    ));
+    let tile_mesh = if rules.board_topology().is_hex() {
+        // Pointy-top hexagon, with opposite edges one tile size apart.
+        let hexagon = RegularPolygon::new(CheckedGameRules::tile_size() / 3f32.sqrt(), 6);
+
+        meshes.add(
+            Mesh::from(Extrusion::new(hexagon, CheckedGameRules::tile_height()))
+                .rotated_by(Quat::from_rotation_x(-FRAC_PI_2)),
+        )
+    } else {
+        meshes.add(Cuboid::new(
+            CheckedGameRules::tile_size(),
+            CheckedGameRules::tile_height(),
+            CheckedGameRules::tile_size(),
+        ))
+    };
 
     // Spawn tiles
     for col in 0..rules.board_cols() {
@@ -65,8 +76,18 @@ pub fn spawn_board(
             // Tile position
             let pos = Pos::new(row, col);
 
-            // Choose color based on position
-            let base_color = if (col + row) % 2 == 0 {
+            if !rules.board_contains(pos) {
+                continue;
+            }
+
+            // Choose color based on position, hex tiles use three colors so neighbours differ
+            let base_color = if rules.board_topology().is_hex() {
+                match (col - row).rem_euclid(3) {
+                    0 => assets.materials.common.tile_white.clone(),
+                    1 => assets.materials.common.tile_gray.clone(),
+                    _ => assets.materials.common.tile_black.clone(),
+                }
+            } else if (col + row) % 2 == 0 {
                 assets.materials.common.tile_white.clone()
             } else {
                 assets.materials.common.tile_black.clone()
@@ -74,7 +95,7 @@ pub fn spawn_board(
 
             let tile_root = commands
                 .spawn((
-                    pos_translation(pos, rules),
+                    pos_translation(pos, 0, rules),
                     GlobalTransform::default(),
                     Tile::new(pos),
                 ))
@@ -131,17 +152,38 @@ pub fn spawn_board(
     (board_root, tiles)
 }
 
+/// Vertical distance between stacked pieces.
+const STACK_SPACING: f32 = CheckedGameRules::tile_size() * 0.5;
+
 /// Converts a logical board position to board space translation.
 ///
 /// (0, 0) is the bottom-left tile on the board.
-pub fn pos_translation(pos: Pos, rules: &CheckedGameRules) -> Transform {
-    const fn half_len(cols_or_rows: i64) -> f32 {
-        (cols_or_rows as f32 - 1.0) * CheckedGameRules::tile_size() / 2.0
+/// `level` is the index of a piece in its stack, counted from the bottom.
+pub fn pos_translation(pos: Pos, level: usize, rules: &CheckedGameRules) -> Transform {
+    const fn half_len(cols_or_rows: i64, spacing: f32) -> f32 {
+        (cols_or_rows as f32 - 1.0) * spacing / 2.0
     }
 
-    Transform::from_translation(Vec3::new(
-        pos.col() as f32 * CheckedGameRules::tile_size() - half_len(rules.board_cols()),
-        0.0,
-        half_len(rules.board_rows()) - pos.row() as f32 * CheckedGameRules::tile_size(),
-    ))
+    let size = CheckedGameRules::tile_size();
+    let y = level as f32 * STACK_SPACING;
+
+    if rules.board_topology().is_hex() {
+        // Axial coordinates: each row shifts half a tile along the columns.
+        let row_spacing = size * 3f32.sqrt() / 2.0;
+        let x = (pos.col() as f32 + pos.row() as f32 / 2.0) * size;
+        let half_width =
+            half_len(rules.board_cols(), size) + half_len(rules.board_rows(), size) / 2.0;
+
+        Transform::from_translation(Vec3::new(
+            x - half_width,
+            y,
+            half_len(rules.board_rows(), row_spacing) - pos.row() as f32 * row_spacing,
+        ))
+    } else {
+        Transform::from_translation(Vec3::new(
+            pos.col() as f32 * size - half_len(rules.board_cols(), size),
+            y,
+            half_len(rules.board_rows(), size) - pos.row() as f32 * size,
+        ))
+    }
 }
@@ -0,0 +1,77 @@
+use crate::states::playing::{
+    phases::{GamePhase, moving::cancel_move},
+    piece::MovingPiece,
+    session::GameSession,
+};
+use bevy::prelude::*;
+
+/// Marks a turn ended by a fallen flag rather than an action.
+#[derive(Resource)]
+pub struct FlagFall;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GamePhase::Selecting), start_clock)
+            .add_systems(
+                Update,
+                tick_clock.run_if(
+                    in_state(GamePhase::Selecting)
+                        .or(in_state(GamePhase::Moving))
+                        .or(in_state(GamePhase::Placing)),
+                ),
+            )
+            .add_systems(OnEnter(GamePhase::TurnEnd), charge_clock)
+            .add_systems(OnEnter(GamePhase::GameOver), pause_clock);
+    }
+}
+
+/// Starts the current player's clock, finishing the previous player's turn.
+fn start_clock(mut session: ResMut<GameSession>, time: Res<Time<Real>>) {
+    let session = session.as_mut();
+    let (color, _) = session.players.get_by_index(session.turn.current_player());
+
+    session.clocks.start(color, time.as_ref());
+}
+
+/// Charges the running clock, and ends the turn when the flag falls.
+///
+/// Whether a fallen flag loses the game is left to the rules, through `RemainingMillis`.
+fn tick_clock(
+    mut commands: Commands,
+    mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    time: Res<Time<Real>>,
+    phase: Res<State<GamePhase>>,
+    moving: Option<Res<MovingPiece>>,
+) {
+    if let NextState::Pending(_) = *next_phase {
+        return;
+    }
+
+    if !session.clocks.tick(time.as_ref()) {
+        return;
+    }
+
+    // Put back the piece being moved.
+    if let (GamePhase::Moving, Some(data)) = (phase.get(), moving) {
+        cancel_move(&mut session, &mut next_phase, &data);
+    }
+
+    commands.insert_resource(FlagFall);
+    next_phase.set(GamePhase::TurnEnd);
+}
+
+/// Charges the running clock for the last action.
+///
+/// The clock keeps running until the next player starts, so players acting again are not
+/// given the increment twice.
+fn charge_clock(mut session: ResMut<GameSession>, time: Res<Time<Real>>) {
+    session.clocks.tick(time.as_ref());
+}
+
+/// Stops the clocks once the game is over.
+fn pause_clock(mut session: ResMut<GameSession>, time: Res<Time<Real>>) {
+    session.clocks.pause(time.as_ref());
+}
@@ -0,0 +1,180 @@
+//! A minimal Markdown renderer for texts written in the rules.
+//!
+//! Supports headings, paragraphs, bullet and numbered lists, fenced code blocks,
+//! `**strong**`, `*emphasis*` and `` `code` ``. Other syntax is shown as written.
+
+use bevy_egui::egui::{
+    self, FontId, RichText,
+    text::{LayoutJob, TextFormat},
+};
+
+const TEXT_SIZE: f32 = 16.0;
+
+/// Shows a Markdown text.
+pub fn markdown(ui: &mut egui::Ui, text: &str) {
+    let mut paragraph = String::new();
+    let mut code: Option<String> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        // Fenced code blocks are shown as written.
+        if trimmed.starts_with("```") {
+            match code.take() {
+                Some(block) => {
+                    ui.code(block.trim_end());
+                }
+                None => {
+                    flush(ui, &mut paragraph);
+                    code = Some(String::new());
+                }
+            }
+
+            continue;
+        }
+
+        if let Some(block) = &mut code {
+            block.push_str(line);
+            block.push('\n');
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(ui, &mut paragraph);
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush(ui, &mut paragraph);
+
+            let size = match level {
+                1 => 24.0,
+                2 => 20.0,
+                _ => 18.0,
+            };
+
+            ui.add_space(6.0);
+            ui.label(RichText::new(heading).size(size).strong());
+        } else if let Some((marker, item)) = list_item(trimmed) {
+            flush(ui, &mut paragraph);
+
+            ui.horizontal_wrapped(|ui| {
+                ui.label(RichText::new(marker).size(TEXT_SIZE));
+                ui.label(inline(ui, item));
+            });
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+
+            paragraph.push_str(trimmed);
+        }
+    }
+
+    // An unclosed code block runs to the end of the text.
+    if let Some(block) = code {
+        ui.code(block.trim_end());
+    }
+
+    flush(ui, &mut paragraph);
+}
+
+/// Shows the pending paragraph, if any.
+fn flush(ui: &mut egui::Ui, paragraph: &mut String) {
+    if paragraph.is_empty() {
+        return;
+    }
+
+    ui.label(inline(ui, paragraph));
+    ui.add_space(4.0);
+    paragraph.clear();
+}
+
+/// Splits a heading into its level and text, e.g. `## Pieces`.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// Splits a list item into the marker to show and its text, e.g. `- Rook` or `2. Rook`.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(("•".to_string(), item));
+    }
+
+    let (number, item) = line.split_once(". ")?;
+
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((format!("{number}."), item))
+}
+
+/// Lays out the spans of a line of text.
+fn inline(ui: &egui::Ui, text: &str) -> LayoutJob {
+    let visuals = ui.visuals();
+    let mut job = LayoutJob::default();
+    let (mut strong, mut emphasis) = (false, false);
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("**") {
+            strong = !strong;
+            rest = after;
+            continue;
+        }
+
+        if let Some(after) = rest.strip_prefix('*') {
+            emphasis = !emphasis;
+            rest = after;
+            continue;
+        }
+
+        if let Some((span, after)) = rest
+            .strip_prefix('`')
+            .and_then(|after| after.split_once('`'))
+        {
+            job.append(
+                span,
+                0.0,
+                TextFormat {
+                    font_id: FontId::monospace(TEXT_SIZE),
+                    color: visuals.text_color(),
+                    background: visuals.code_bg_color,
+                    ..Default::default()
+                },
+            );
+
+            rest = after;
+            continue;
+        }
+
+        // Plain text up to the next marker, a lone backtick is kept as written.
+        let first = rest.chars().next().map_or(0, char::len_utf8);
+        let end = rest[first..]
+            .find(['*', '`'])
+            .map_or(rest.len(), |index| index + first);
+
+        job.append(
+            &rest[..end],
+            0.0,
+            TextFormat {
+                font_id: FontId::proportional(TEXT_SIZE),
+                color: if strong {
+                    visuals.strong_text_color()
+                } else {
+                    visuals.text_color()
+                },
+                italics: emphasis,
+                ..Default::default()
+            },
+        );
+
+        rest = &rest[end..];
+    }
+
+    job
+}
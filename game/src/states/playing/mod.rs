@@ -1,15 +1,27 @@
 use crate::{
     assets::GameAssets,
+    locale::add_egui_font,
+    settings::Settings,
     states::{
         AppState,
         game_setup::LoadedRules,
         playing::{
             board::spawn_board,
             camera::PlayingCamera,
+            clock::ClockPlugin,
             phases::GamePhasePlugin,
             piece::place_new_piece,
-            session::{GameSession, PlacedPieceIndex, player::Players, turn::TurnController},
-            ui::{TopPanelText, bottom_panel, top_panel},
+            session::{
+                GameSession, PlacedPieceIndex,
+                clock::Clocks,
+                counters::TurnCounters,
+                player::{PieceSource, Players},
+                random::SessionRng,
+                team::Teams,
+                turn::TurnController,
+                zobrist::PositionHistory,
+            },
+            ui::{TopPanelText, bottom_panel, rules_panel, top_panel},
         },
     },
 };
@@ -18,6 +30,8 @@ use bevy_egui::{EguiGlobalSettings, EguiPrimaryContextPass, PrimaryEguiContext};
 
 pub mod board;
 pub mod camera;
+pub mod clock;
+pub mod markdown;
 pub mod phases;
 pub mod piece;
 pub mod session;
@@ -29,6 +43,7 @@ pub struct PlayingPlugin;
 impl Plugin for PlayingPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(GamePhasePlugin)
+            .add_plugins(ClockPlugin)
             .add_message::<TileEnter>()
             .add_message::<TileOut>()
             .add_message::<TileRelease>()
@@ -37,8 +52,9 @@ impl Plugin for PlayingPlugin {
             .add_systems(OnExit(AppState::Playing), on_exit)
             .add_systems(
                 EguiPrimaryContextPass,
-                (top_panel, bottom_panel).run_if(in_state(AppState::Playing)),
-            );
+                (top_panel, bottom_panel, rules_panel).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(EguiPrimaryContextPass, add_egui_font.before(top_panel));
     }
 }
 
@@ -63,6 +79,7 @@ fn on_enter(
     mut meshes: ResMut<Assets<Mesh>>,
     assets: Res<GameAssets>,
     rules: Res<LoadedRules>,
+    settings: Res<Settings>,
 ) {
     // Disable the automatic creation of a primary context to set it up manually for the camera we need.
     egui_global_settings.auto_create_primary_context = false;
@@ -113,21 +130,43 @@ fn on_enter(
             &mut placed_pieces,
             piece.model(),
             piece.color(),
+            PieceSource::Stock,
             piece.pos(),
         )
         .unwrap();
     }
 
+    let teams = Teams::new(&rules);
+    let rng = match settings.seed {
+        Some(seed) => SessionRng::new(seed),
+        None => SessionRng::from_time(),
+    };
+
+    let turn = TurnController::new(rules.turn_schedule(), players.num(), rng);
+
     // Create game session
-    let session = GameSession {
+    let mut session = GameSession {
         board,
         tiles,
         placed_pieces,
         players,
-        turn: TurnController::new(),
+        teams,
+        turn,
         last_action: None,
+        history: PositionHistory::new(),
+        counters: TurnCounters::new(),
+        clocks: Clocks::new(&rules),
+        wrap: rules.board_wrap_lengths(),
     };
 
+    // The starting position counts towards repetitions.
+    let (first, _) = session.players.get_by_index(session.turn.current_player());
+    let hash = session.position_hash(first, session.turn.actions_left());
+    session.history.record(hash);
+
+    // Initial scores
+    session.update_scores(&rules).unwrap();
+
     // Insert resources
     commands.insert_resource(TopPanelText(Default::default()));
     commands.insert_resource(session);
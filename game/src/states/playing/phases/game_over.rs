@@ -1,14 +1,31 @@
-use crate::states::playing::{
-    TopPanelText, camera::PlayingCamera, phases::GamePhase, session::GameSession,
+use crate::{
+    assets::GameAssets,
+    locale::fill,
+    settings::Settings,
+    states::{
+        AppState,
+        error::CurrentError,
+        game_setup::LoadedRules,
+        playing::{
+            TopPanelText, camera::PlayingCamera, phases::GamePhase, piece::update_piece_visibility,
+            session::GameSession, ui::top_panel,
+        },
+    },
 };
 use bevy::{input::mouse::MouseWheel, prelude::*};
-use bevy_egui::EguiContexts;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 
 pub struct GameOverPlugin;
 
 impl Plugin for GameOverPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GamePhase::GameOver), on_enter)
+            .add_systems(
+                EguiPrimaryContextPass,
+                results_panel
+                    .after(top_panel)
+                    .run_if(in_state(GamePhase::GameOver)),
+            )
             .add_systems(
                 Update,
                 (on_mouse_wheel, on_pointer_drag).run_if(in_state(GamePhase::GameOver)),
@@ -18,11 +35,18 @@ impl Plugin for GameOverPlugin {
 }
 
 fn on_enter(
+    mut commands: Commands,
     mut drag: Option<ResMut<Messages<Pointer<Drag>>>>,
     mut wheel: Option<ResMut<Messages<Pointer<MouseWheel>>>>,
+    assets: Res<GameAssets>,
+    rules: Res<LoadedRules>,
     session: Res<GameSession>,
+    settings: Res<Settings>,
     mut top_panel_text: ResMut<TopPanelText>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
+    let strings = settings.locale.strings();
+
     // Clear messages
     // In case the old messages are still in the queue
     if let Some(drag) = &mut drag {
@@ -33,7 +57,101 @@ fn on_enter(
         wheel.clear();
     }
 
-    top_panel_text.0 = format!("Game Over: {}", session.players.player_states_message());
+    // Reveal all pieces.
+    if let Err(err) = update_piece_visibility(&mut commands, &assets, &rules, &session, None) {
+        commands.insert_resource(CurrentError(err));
+        next_state.set(AppState::Error);
+        return;
+    }
+
+    let players_message = session.players.player_states_message(&rules, strings);
+
+    // Report team results first if teams are declared.
+    let results = if rules.teams().next().is_some() {
+        format!(
+            "{} — {}",
+            session.teams.team_states_message(&session.players, strings),
+            players_message
+        )
+    } else {
+        players_message
+    };
+
+    top_panel_text.0 = fill(strings.game_over, &[("results", &results)]);
+}
+
+/// A system that shows the final standings below the top panel.
+fn results_panel(
+    mut egui: EguiContexts,
+    rules: Res<LoadedRules>,
+    session: Res<GameSession>,
+    settings: Res<Settings>,
+) {
+    let strings = settings.locale.strings();
+    let has_scores = session.players.has_scores();
+
+    egui::TopBottomPanel::top("results_panel")
+        .frame(
+            egui::Frame::NONE
+                .fill(egui::Color32::from_rgba_premultiplied(30, 30, 30, 192))
+                .inner_margin(egui::Margin::symmetric(0, 10)),
+        )
+        .show(egui.ctx_mut().unwrap(), |ui| {
+            ui.vertical_centered(|ui| {
+                egui::Grid::new("results_grid")
+                    .num_columns(if has_scores { 4 } else { 3 })
+                    .spacing([40.0, 6.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in [strings.rank, strings.player, strings.result] {
+                            ui.label(egui::RichText::new(header).size(18.0).strong());
+                        }
+
+                        if has_scores {
+                            ui.label(egui::RichText::new(strings.score).size(18.0).strong());
+                        }
+                        ui.end_row();
+
+                        for (rank, color, state) in session.players.standings(rules.tie_break()) {
+                            ui.label(egui::RichText::new(rank.to_string()).size(18.0));
+                            ui.label(egui::RichText::new(rules.color_name(color)).size(18.0));
+                            ui.label(egui::RichText::new(strings.state(state)).size(18.0));
+
+                            if has_scores {
+                                let score = session.players.get_by_color(color).score();
+                                let text = score.map_or("-".to_string(), |score| score.to_string());
+                                ui.label(egui::RichText::new(text).size(18.0));
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                // The seed replays the dice and random draws, given the same actions.
+                let mut replay = fill(strings.seed, &[("seed", &session.turn.seed())]);
+
+                if !session.turn.dice_rolls().is_empty() {
+                    let rolls: Vec<String> = session
+                        .turn
+                        .dice_rolls()
+                        .iter()
+                        .map(i64::to_string)
+                        .collect();
+                    let rolls = rolls.join(strings.separator);
+
+                    replay = format!(
+                        "{replay} — {}",
+                        fill(strings.dice_rolls, &[("rolls", &rolls)])
+                    );
+                }
+
+                ui.label(egui::RichText::new(replay).size(14.0).weak());
+
+                // Identifies the final position, e.g. to compare games.
+                let hash = format!("{:016x}", session.history.current());
+                let position = fill(strings.position, &[("hash", &hash)]);
+                ui.label(egui::RichText::new(position).size(14.0).weak());
+            });
+        });
 }
 
 fn on_exit() {
@@ -2,7 +2,7 @@ use crate::states::{
     AppState,
     playing::phases::{
         game_over::GameOverPlugin, moving::MovingPlugin, placing::PlacingPlugin,
-        selecting::SelectingPlugin, turn_end::TurnEndPlugin,
+        promoting::PromotingPlugin, selecting::SelectingPlugin, turn_end::TurnEndPlugin,
     },
 };
 use bevy::prelude::*;
@@ -10,6 +10,7 @@ use bevy::prelude::*;
 pub mod game_over;
 pub mod moving;
 pub mod placing;
+pub mod promoting;
 pub mod selecting;
 pub mod turn_end;
 
@@ -26,6 +27,9 @@ pub enum GamePhase {
     /// The player is placing a new piece on the board.
     Placing,
 
+    /// The player is choosing a model to promote the moved piece to.
+    Promoting,
+
     /// The turn has ended, evaluating win/loss conditions.
     TurnEnd,
 
@@ -41,6 +45,7 @@ impl Plugin for GamePhasePlugin {
             .add_plugins(SelectingPlugin)
             .add_plugins(MovingPlugin)
             .add_plugins(PlacingPlugin)
+            .add_plugins(PromotingPlugin)
             .add_plugins(TurnEndPlugin)
             .add_plugins(GameOverPlugin);
     }
@@ -1,47 +1,57 @@
-use crate::states::{
-    game_setup::LoadedRules,
-    playing::{
-        TileEnter, TileOut, TileRelease,
-        board::pos_translation,
-        phases::GamePhase,
-        piece::{MovingPiece, PiecePos, PlacedPiece, capture_piece},
-        session::{GameSession, PlacedPieceIndex, player::Players, turn::TurnController},
-        tile::Tile,
+use crate::{
+    assets::GameAssets,
+    expr_contexts::movement::MovementContext,
+    states::{
+        game_setup::LoadedRules,
+        playing::{
+            TileEnter, TileOut, TileRelease,
+            board::pos_translation,
+            phases::GamePhase,
+            piece::{MovingPiece, PiecePos, PromotingPiece, capture_piece, promote_piece},
+            session::{
+                GameSession, PlacedPieceIndex, player::Players, random::RandomCalls,
+                turn::TurnController,
+            },
+            tile::Tile,
+        },
     },
 };
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 use bevy_tweening::{AnimTarget, Lens, Tween, TweenAnim};
-use rulery::pos::Pos;
-use std::{collections::hash_map::Entry, time::Duration};
+use rulery::{capture::CaptureMode, pos::Pos, stack::StackMode};
+use std::time::Duration;
 
 pub fn start_move_piece(
     commands: &mut Commands,
     placed_piece_index: &mut PlacedPieceIndex,
     players: &Players,
     turn: &TurnController,
+    stacking: StackMode,
     next_phase: &mut NextState<GamePhase>,
     at: Pos,
 ) {
-    let Entry::Occupied(entry) = placed_piece_index.entry(at) else {
+    let Some(top) = placed_piece_index.get(at) else {
         panic!("No placed piece at position: {:?}", at);
     };
 
-    // If the piece color does not match the current player's color, do nothing
-    if players.get_by_index(turn.current_player()).0 != entry.get().color() {
+    // If the top piece color does not match the current player's color, do nothing
+    if players.get_by_index(turn.current_player()).0 != top.color() {
         return;
     }
 
-    // Remove the record from the placed piece index
-    let placed = entry.remove();
+    // Remove the records from the placed piece index
+    let mut carried = match stacking {
+        StackMode::WholeStack => placed_piece_index.take(at),
+        StackMode::Disabled | StackMode::TopPiece => {
+            placed_piece_index.pop(at).into_iter().collect()
+        }
+    };
+
+    let placed = carried.pop().unwrap();
 
     // Enter moving state
-    commands.insert_resource(MovingPiece::new(
-        placed.model(),
-        placed.color(),
-        placed.pos(),
-        placed.entities().clone(),
-    ));
+    commands.insert_resource(MovingPiece::new(&placed, carried));
 
     next_phase.set(GamePhase::Moving);
 }
@@ -95,10 +105,11 @@ fn on_enter(
         pointer_press.clear();
     }
 
-    let rules = rules.get_piece(data.model()).unwrap();
+    let piece_rules = rules.get_piece(data.model()).unwrap();
 
     // Collect movable tiles
-    data.collect_movable(&session, tile_query, rules).unwrap();
+    data.collect_movable(&session, tile_query, piece_rules, rules.stacking())
+        .unwrap();
 
     // Highlight the moving piece
     vis_query
@@ -152,6 +163,7 @@ fn on_tile_release(
     child_query: Query<&ChildOf>,
     tile_query: Query<&Tile>,
     mut piece_query: Query<(&Transform, &mut PiecePos)>,
+    assets: Res<GameAssets>,
     rules: Res<LoadedRules>,
     mut session: ResMut<GameSession>,
     mut next_phase: ResMut<NextState<GamePhase>>,
@@ -179,59 +191,120 @@ fn on_tile_release(
     let tile = tile_query.get(child.parent()).unwrap();
 
     if data.can_move_to(tile.pos()) {
-        // If the target position is already occupied, capture it.
-        capture_piece(
-            &mut commands,
-            &mut session.placed_pieces,
-            &mut session.players,
-            tile.pos(),
-        );
-
-        let (transform, mut piece_pos) = piece_query.get_mut(data.entities().root()).unwrap();
-
-        // Animation
-        {
-            let start = transform.translation;
-            let end = pos_translation(tile.pos(), &rules).translation;
-
-            // Determine the height of the parabola
-            // The height is 25% of the distance between start and end
-            let dist = start.distance(end);
-            let height = dist * 0.25;
-
-            let tween = Tween::new(
-                EaseFunction::SineInOut,
-                Duration::from_millis(220),
-                TransformParabolaLens {
-                    start,
-                    end,
-                    up: Vec3::Y,
-                    height,
-                },
-            );
-
-            commands.spawn((
-                TweenAnim::new(tween),
-                AnimTarget::component::<Transform>(data.entities().root()),
-            ));
+        let piece_rules = rules.get_piece(data.model()).unwrap();
+
+        let captured = data
+            .collect_captured(
+                session,
+                piece_rules,
+                rules.stacking(),
+                rules.board_topology(),
+                tile.pos(),
+            )
+            .unwrap();
+
+        // A ranged capture takes the target without moving the piece.
+        let dest = if piece_rules.capture_mode() == CaptureMode::Ranged && !captured.is_empty() {
+            data.source_pos()
+        } else {
+            tile.pos()
+        };
+
+        // Remove the captured pieces.
+        for pos in captured {
+            if capture_piece(
+                &mut commands,
+                &rules,
+                &mut session.placed_pieces,
+                &mut session.players,
+                data.color(),
+                pos,
+            ) {
+                session.counters.record_capture(session.turn.turn_number());
+            }
         }
 
-        // Update piece pos
-        piece_pos.0 = tile.pos();
+        session
+            .counters
+            .record_move(data.model(), session.turn.turn_number());
 
-        // Add record to the placed piece index at the current position
-        session.placed_pieces.insert(
-            tile.pos(),
-            PlacedPiece::new(
-                data.model(),
-                data.color(),
-                tile.pos(),
-                data.entities().clone(),
-            ),
-        );
+        // The moving pieces go on top of the stack at the destination, carried ones first.
+        let moved = data.carried().iter().cloned().chain([data.to_placed()]);
+
+        for mut placed in moved {
+            let root = placed.entities().root();
+            let (transform, mut piece_pos) = piece_query.get_mut(root).unwrap();
+
+            let start = transform.translation;
+            let level = session.placed_pieces.height(dest);
+            let end = pos_translation(dest, level, &rules).translation;
+
+            // Animation
+            if start != end {
+                // Determine the height of the parabola
+                // The height is 25% of the distance between start and end
+                let dist = start.distance(end);
+                let height = dist * 0.25;
+
+                let tween = Tween::new(
+                    EaseFunction::SineInOut,
+                    Duration::from_millis(220),
+                    TransformParabolaLens {
+                        start,
+                        end,
+                        up: Vec3::Y,
+                        height,
+                    },
+                );
+
+                commands.spawn((
+                    TweenAnim::new(tween),
+                    AnimTarget::component::<Transform>(root),
+                ));
+            }
+
+            // Update piece pos
+            piece_pos.0 = dest;
+            placed.set_pos(dest);
+
+            // Add record to the placed piece index at the current position
+            session.placed_pieces.push(placed);
+        }
 
         // Update last action position
-        session.last_action = Some(tile.pos());
+        session.last_action = Some(dest);
+
+        // Check promotion
+        if let Some(promotion) = piece_rules.promotion() {
+            let ctx = MovementContext {
+                session,
+                random_calls: RandomCalls::default(),
+                moving_model: data.model(),
+                moving_color: data.color(),
+                source_pos: data.source_pos(),
+                target_pos: dest,
+            };
+
+            if promotion.can_promote(&ctx).unwrap() {
+                if let [model] = promotion.models() {
+                    // Only one choice, promote automatically.
+                    promote_piece(
+                        &mut commands,
+                        &assets,
+                        &mut session.placed_pieces,
+                        dest,
+                        *model,
+                    )
+                    .unwrap();
+                } else {
+                    // Let the player choose.
+                    commands
+                        .insert_resource(PromotingPiece::new(dest, promotion.models().to_vec()));
+                    next_phase.set(GamePhase::Promoting);
+                    return;
+                }
+            }
+        }
 
         // Finish this turn
         next_phase.set(GamePhase::TurnEnd);
@@ -352,16 +425,12 @@ fn on_escape_cancel(
     cancel_move(&mut session, &mut next_phase, &data);
 }
 
-fn cancel_move(session: &mut GameSession, next: &mut NextState<GamePhase>, data: &MovingPiece) {
-    session.placed_pieces.insert(
-        data.source_pos(),
-        PlacedPiece::new(
-            data.model(),
-            data.color(),
-            data.source_pos(),
-            data.entities().clone(),
-        ),
-    );
+pub fn cancel_move(session: &mut GameSession, next: &mut NextState<GamePhase>, data: &MovingPiece) {
+    for placed in data.carried() {
+        session.placed_pieces.push(placed.clone());
+    }
+
+    session.placed_pieces.push(data.to_placed());
 
     next.set(GamePhase::Selecting);
 }
@@ -7,7 +7,7 @@ use crate::{
             phases::GamePhase,
             piece::{PlacingPiece, capture_piece},
             place_new_piece,
-            session::GameSession,
+            session::{GameSession, player::PieceSource},
             tile::Tile,
         },
     },
@@ -21,8 +21,9 @@ pub fn start_place_piece(
     next_phase: &mut NextState<GamePhase>,
     model: PieceModel,
     color: PieceColor,
+    source: PieceSource,
 ) {
-    commands.insert_resource(PlacingPiece::new(model, color));
+    commands.insert_resource(PlacingPiece::new(model, color, source));
     next_phase.set(GamePhase::Placing);
 }
 
@@ -148,13 +149,20 @@ fn on_tile_release(
     let tile = tile_query.get(child.parent()).unwrap();
 
     if data.can_place_at(tile.pos()) {
-        // If the to place position is already occupied, remove the existing piece (i.e. capture it)
-        capture_piece(
-            &mut commands,
-            &mut session.placed_pieces,
-            &mut session.players,
-            tile.pos(),
-        );
+        // If the to place position is already occupied, remove the existing piece (i.e. capture it),
+        // unless the new piece goes on top of the stack
+        if !rules.stacking().is_enabled()
+            && capture_piece(
+                &mut commands,
+                &rules,
+                &mut session.placed_pieces,
+                &mut session.players,
+                data.color(),
+                tile.pos(),
+            )
+        {
+            session.counters.record_capture(session.turn.turn_number());
+        }
 
         // Spawn the placed piece at the target position
         place_new_piece(
@@ -166,10 +174,15 @@ fn on_tile_release(
             &mut session.placed_pieces,
             data.model(),
             data.color(),
+            data.source(),
             tile.pos(),
         )
         .unwrap();
 
+        session
+            .counters
+            .record_placement(session.turn.turn_number());
+
         // Update last action position
         session.last_action = Some(tile.pos());
 
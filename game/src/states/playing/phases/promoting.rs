@@ -0,0 +1,90 @@
+use crate::{
+    assets::GameAssets,
+    settings::Settings,
+    states::{
+        game_setup::LoadedRules,
+        playing::{
+            TopPanelText,
+            phases::GamePhase,
+            piece::{PromotingPiece, promote_piece},
+            session::GameSession,
+        },
+    },
+};
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+pub struct PromotingPlugin;
+
+impl Plugin for PromotingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GamePhase::Promoting), on_enter)
+            .add_systems(
+                EguiPrimaryContextPass,
+                promotion_window.run_if(in_state(GamePhase::Promoting)),
+            )
+            .add_systems(OnExit(GamePhase::Promoting), on_exit);
+    }
+}
+
+fn on_enter(mut top_panel_text: ResMut<TopPanelText>, settings: Res<Settings>) {
+    top_panel_text.0 = settings.locale.strings().choose_promotion.to_string();
+}
+
+fn on_exit(mut commands: Commands) {
+    commands.remove_resource::<PromotingPiece>();
+}
+
+/// A system that shows the promotion chooser.
+fn promotion_window(
+    mut commands: Commands,
+    mut egui: EguiContexts,
+    assets: Res<GameAssets>,
+    rules: Res<LoadedRules>,
+    mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    data: Res<PromotingPiece>,
+    settings: Res<Settings>,
+) {
+    if let NextState::Pending(_) = *next_phase {
+        return;
+    }
+
+    let mut chosen = None;
+
+    egui::Window::new(settings.locale.strings().promotion)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(egui.ctx_mut().unwrap(), |ui| {
+            ui.horizontal(|ui| {
+                for model in data.models() {
+                    let button = egui::Button::new(
+                        egui::RichText::new(rules.model_name(model))
+                            .size(18.0)
+                            .strong(),
+                    );
+
+                    if ui.add(button).clicked() {
+                        chosen = Some(model);
+                    }
+                }
+            });
+        });
+
+    let Some(model) = chosen else {
+        return;
+    };
+
+    promote_piece(
+        &mut commands,
+        &assets,
+        &mut session.placed_pieces,
+        data.pos(),
+        model,
+    )
+    .unwrap();
+
+    // Finish this turn
+    next_phase.set(GamePhase::TurnEnd);
+}
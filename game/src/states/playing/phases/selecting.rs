@@ -1,9 +1,18 @@
-use crate::states::playing::{
-    PiecePress, TopPanelText,
-    camera::PlayingCamera,
-    phases::{GamePhase, moving::start_move_piece},
-    piece::PiecePos,
-    session::GameSession,
+use crate::{
+    assets::GameAssets,
+    settings::Settings,
+    states::{
+        AppState,
+        error::CurrentError,
+        game_setup::LoadedRules,
+        playing::{
+            PiecePress, TopPanelText,
+            camera::PlayingCamera,
+            phases::{GamePhase, moving::start_move_piece},
+            piece::{PiecePos, update_piece_visibility},
+            session::GameSession,
+        },
+    },
 };
 use bevy::{input::mouse::MouseWheel, prelude::*};
 use bevy_egui::EguiContexts;
@@ -23,11 +32,16 @@ impl Plugin for SelectingPlugin {
 }
 
 fn on_enter(
+    mut commands: Commands,
     mut piece_press: Option<ResMut<Messages<PiecePress>>>,
     mut drag: Option<ResMut<Messages<Pointer<Drag>>>>,
     mut wheel: Option<ResMut<Messages<Pointer<MouseWheel>>>>,
+    assets: Res<GameAssets>,
+    rules: Res<LoadedRules>,
     session: Res<GameSession>,
+    settings: Res<Settings>,
     mut top_panel_text: ResMut<TopPanelText>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
     // Clear messages
     // In case the old messages are still in the queue
@@ -43,7 +57,20 @@ fn on_enter(
         wheel.clear();
     }
 
-    top_panel_text.0 = session.turn.turn_message(&session.players);
+    top_panel_text.0 =
+        session
+            .turn
+            .turn_message(&rules, &session.players, settings.locale.strings());
+
+    // Show the board as the current player sees it (hot-seat).
+    let (viewer, _) = session.players.get_by_index(session.turn.current_player());
+
+    if let Err(err) =
+        update_piece_visibility(&mut commands, &assets, &rules, &session, Some(viewer))
+    {
+        commands.insert_resource(CurrentError(err));
+        next_state.set(AppState::Error);
+    }
 }
 
 fn on_exit() {
@@ -105,6 +132,7 @@ fn on_piece_press(
     mut commands: Commands,
     child_query: Query<&ChildOf>,
     piece_query: Query<&PiecePos>,
+    rules: Res<LoadedRules>,
     mut session: ResMut<GameSession>,
     mut next_phase: ResMut<NextState<GamePhase>>,
 ) {
@@ -131,6 +159,7 @@ fn on_piece_press(
         &mut session.placed_pieces,
         &session.players,
         &session.turn,
+        rules.stacking(),
         &mut next_phase,
         pos.0,
     );
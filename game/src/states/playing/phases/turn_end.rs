@@ -1,24 +1,88 @@
 use crate::{
     GameError,
-    expr_contexts::{game_over::GameOverContext, win_or_lose::WinOrLoseContext},
+    expr_contexts::{
+        game_over::GameOverContext, turn_order::TurnOrderContext, win_or_lose::WinOrLoseContext,
+    },
     states::{
         AppState,
         error::CurrentError,
         game_setup::LoadedRules,
-        playing::{phases::GamePhase, session::GameSession},
+        playing::{
+            clock::FlagFall,
+            phases::GamePhase,
+            session::{GameSession, random::RandomCalls, turn::NextTurn},
+        },
     },
 };
 use bevy::prelude::*;
-use rulery::player::PlayerState;
+use rulery::{CheckedGameRules, piece::PieceColor, player::PlayerState};
 
 pub struct TurnEndPlugin;
 
 impl Plugin for TurnEndPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, evaluate_turn.run_if(in_state(GamePhase::TurnEnd)));
+        app.add_systems(OnEnter(GamePhase::TurnEnd), record_position)
+            .add_systems(Update, evaluate_turn.run_if(in_state(GamePhase::TurnEnd)));
     }
 }
 
+/// Records the position reached by the last action, keyed by the player to act next and the
+/// actions they have left.
+///
+/// Players have not been evaluated yet, so the next player is found among those active before
+/// the action. Nothing is recorded when the flag fell, as no action was made.
+fn record_position(
+    mut commands: Commands,
+    mut session: ResMut<GameSession>,
+    rules: Res<LoadedRules>,
+    flag_fall: Option<Res<FlagFall>>,
+) {
+    if flag_fall.is_some() {
+        commands.remove_resource::<FlagFall>();
+        return;
+    }
+
+    let session = session.as_mut();
+    let schedule = rules.turn_schedule();
+
+    let next = if session.turn.actions_left() > 1 {
+        Ok(None)
+    } else {
+        find_next_player(session, &rules).map(Some)
+    };
+
+    let (to_move, actions_left) = match next {
+        Ok(next) => session.turn.to_move(schedule, next),
+        // Nobody can act next, the game is about to end.
+        Err(_) => (session.turn.current_player(), 0),
+    };
+
+    let (color, _) = session.players.get_by_index(to_move);
+    let hash = session.position_hash(color, actions_left);
+
+    session.history.record(hash);
+}
+
+/// Finds the player taking the next turn among the active players.
+fn find_next_player(
+    session: &GameSession,
+    rules: &CheckedGameRules,
+) -> Result<NextTurn, GameError> {
+    let schedule = rules.turn_schedule();
+
+    session
+        .turn
+        .next_player(schedule, &session.players, |candidate_color| {
+            let ctx = TurnOrderContext {
+                session,
+                random_calls: RandomCalls::default(),
+                candidate_color,
+            };
+
+            schedule.is_next_player(&ctx)
+        })
+}
+
 /// A system that evaluates win/loss conditions, and prepares for the next turn or ends the game.
 fn evaluate_turn(
     mut commands: Commands,
@@ -38,47 +102,117 @@ fn evaluate_turn(
     let session = session.as_mut();
 
     // Evaluates state for each active player.
-    for (piece_color, player) in session
+    let states: Vec<(PieceColor, PlayerState)> = session
         .players
-        .iter_mut()
+        .iter()
         .filter(|(_, player)| player.state() == PlayerState::Active)
-    {
-        let ctx = WinOrLoseContext {
-            turn: &session.turn,
-            last_action: &session.last_action,
-            placed_piece_index: &session.placed_pieces,
-        };
-
-        player.set_state(
-            rules
+        .map(|(piece_color, _)| {
+            let ctx = WinOrLoseContext {
+                session,
+                random_calls: RandomCalls::default(),
+            };
+
+            let state = rules
                 .get_player(piece_color)
                 .unwrap()
                 .evaluate_state(&ctx)
-                .unwrap(),
-        );
+                .unwrap();
+
+            (piece_color, state)
+        })
+        .collect();
+
+    for (piece_color, state) in states {
+        let player = session.players.get_by_color_mut(piece_color);
+
+        // Record when players lose to rank them at the end.
+        match state {
+            PlayerState::Lost => player.lose(session.turn.turn_number()),
+            state => player.set_state(state),
+        }
     }
 
-    let ctx = GameOverContext { session };
+    // A win is shared by the whole team.
+    let winners: Vec<PieceColor> = session
+        .players
+        .iter()
+        .filter(|(_, player)| player.state() == PlayerState::Won)
+        .map(|(color, _)| color)
+        .collect();
+
+    for color in winners {
+        for ally in session.teams.allies(color) {
+            let player = session.players.get_by_color_mut(*ally);
+
+            if player.state() == PlayerState::Active {
+                player.set_state(PlayerState::Won);
+            }
+        }
+    }
+
+    // Update scores before the end conditions are checked.
+    if let Err(err) = session.update_scores(&rules) {
+        commands.insert_resource(CurrentError(err));
+        next_state.set(AppState::Error);
+        return;
+    }
+
+    let ctx = GameOverContext {
+        session,
+        random_calls: RandomCalls::default(),
+    };
+
+    // Check draw condition
+    if rules.evaluate_draw_condition(&ctx).unwrap() {
+        for (_, player) in session
+            .players
+            .iter_mut()
+            .filter(|(_, player)| player.state() == PlayerState::Active)
+        {
+            player.set_state(PlayerState::Draw);
+        }
+
+        // Game over.
+        next_phase.set(GamePhase::GameOver);
+        return;
+    }
+
+    let ctx = GameOverContext {
+        session,
+        random_calls: RandomCalls::default(),
+    };
 
     // Check game over condition
     if rules.evaluate_game_over_condition(&ctx).unwrap() {
         // Game over.
         next_phase.set(GamePhase::GameOver);
-    } else {
-        // Advance the turn
-        match session.turn.advance_turn(&session.players) {
-            Ok(()) => {
-                // Start next turn.
-                next_phase.set(GamePhase::Selecting);
-            }
-            Err(GameError::NoActivePlayer) => {
-                // Game over.
-                next_phase.set(GamePhase::GameOver);
-            }
-            Err(err) => {
-                commands.insert_resource(CurrentError(err));
-                next_state.set(AppState::Error);
-            }
+        return;
+    }
+
+    // Keep the turn if the current player is still active and has actions left.
+    let (_, current) = session.players.get_by_index(session.turn.current_player());
+
+    if session.turn.record_action() && current.state() == PlayerState::Active {
+        next_phase.set(GamePhase::Selecting);
+        return;
+    }
+
+    // Find the next player
+    let schedule = rules.turn_schedule();
+
+    match find_next_player(session, &rules) {
+        Ok(next) => {
+            // Start next turn.
+            session.turn.advance_turn(schedule, next);
+            next_phase.set(GamePhase::Selecting);
+        }
+        Err(GameError::NoActivePlayer) => {
+            // Game over.
+            next_phase.set(GamePhase::GameOver);
+        }
+        Err(err) => {
+            commands.insert_resource(CurrentError(err));
+            next_state.set(AppState::Error);
         }
     }
 }
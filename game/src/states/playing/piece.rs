@@ -1,11 +1,18 @@
 use crate::{
     GameError,
     assets::GameAssets,
-    expr_contexts::{movement::MovementContext, placement::PlacementContext},
+    expr_contexts::{
+        capture::CaptureContext, movement::MovementContext, placement::PlacementContext,
+        visibility::VisibilityContext,
+    },
     states::playing::{
         PiecePress,
         board::pos_translation,
-        session::{GameSession, PlacedPieceIndex, player::Players},
+        session::{
+            GameSession, PlacedPieceIndex,
+            player::{PieceSource, Players},
+            random::RandomCalls,
+        },
         tile::Tile,
     },
 };
@@ -16,13 +23,13 @@ use bevy_tweening::{
 };
 use rulery::{
     CheckedGameRules,
+    board::Topology,
+    capture::CaptureMode,
     piece::{PieceColor, PieceModel, PieceRules},
     pos::Pos,
+    stack::StackMode,
 };
-use std::{
-    collections::{HashSet, hash_map::Entry},
-    time::Duration,
-};
+use std::{collections::HashSet, time::Duration};
 
 /// Entities associated with a piece.
 #[derive(Debug, Clone)]
@@ -61,6 +68,10 @@ impl PieceEntities {
 #[derive(Debug, Clone)]
 pub struct PlacedPiece {
     model: PieceModel,
+
+    /// The model the piece was placed as, kept through promotions.
+    placed_as: PieceModel,
+
     color: PieceColor,
     pos: Pos,
     entities: PieceEntities,
@@ -71,6 +82,7 @@ impl PlacedPiece {
     pub fn new(model: PieceModel, color: PieceColor, pos: Pos, entities: PieceEntities) -> Self {
         Self {
             model,
+            placed_as: model,
             color,
             pos,
             entities,
@@ -82,6 +94,16 @@ impl PlacedPiece {
         self.model
     }
 
+    /// Returns the model the piece was placed as, which its stock is counted against.
+    pub fn placed_as(&self) -> PieceModel {
+        self.placed_as
+    }
+
+    /// Sets the piece model.
+    pub fn set_model(&mut self, model: PieceModel) {
+        self.model = model;
+    }
+
     /// Returns the piece color.
     pub fn color(&self) -> PieceColor {
         self.color
@@ -92,6 +114,11 @@ impl PlacedPiece {
         self.pos
     }
 
+    /// Sets the position of the placed piece.
+    pub fn set_pos(&mut self, pos: Pos) {
+        self.pos = pos;
+    }
+
     /// Returns the piece entities.
     pub fn entities(&self) -> &PieceEntities {
         &self.entities
@@ -101,20 +128,27 @@ impl PlacedPiece {
 #[derive(Debug, Resource)]
 pub struct MovingPiece {
     model: PieceModel,
+    placed_as: PieceModel,
     color: PieceColor,
     source: Pos,
     entities: PieceEntities,
+
+    /// Pieces under the moving piece that move with it, ordered from bottom to top.
+    carried: Vec<PlacedPiece>,
+
     movable: HashSet<Pos>,
 }
 
 impl MovingPiece {
-    /// Creates a new moving piece.
-    pub fn new(model: PieceModel, color: PieceColor, source: Pos, entities: PieceEntities) -> Self {
+    /// Creates a new moving piece from the piece picked up.
+    pub fn new(placed: &PlacedPiece, carried: Vec<PlacedPiece>) -> Self {
         Self {
-            model,
-            color,
-            source,
-            entities,
+            model: placed.model,
+            placed_as: placed.placed_as,
+            color: placed.color,
+            source: placed.pos,
+            entities: placed.entities.clone(),
+            carried,
             movable: HashSet::new(),
         }
     }
@@ -125,6 +159,7 @@ impl MovingPiece {
         session: &GameSession,
         tile_query: Query<&Tile>,
         rules: &PieceRules,
+        stacking: StackMode,
     ) -> Result<(), GameError> {
         for tile in tile_query {
             // Skip source tile
@@ -134,20 +169,137 @@ impl MovingPiece {
 
             let ctx = MovementContext {
                 session,
+                random_calls: RandomCalls::default(),
                 moving_model: self.model,
                 moving_color: self.color,
                 source_pos: self.source,
                 target_pos: tile.pos(),
             };
 
-            if rules.can_move(&ctx)? {
-                self.movable.insert(tile.pos());
+            if !rules.can_move(&ctx)? {
+                continue;
             }
+
+            // An occupied target can only be taken if the capture mode and condition allow it.
+            if session.placed_pieces.is_occupied(tile.pos()) {
+                match rules.capture_mode() {
+                    // The piece goes on top of the stack instead.
+                    CaptureMode::Replace
+                        if stacking.is_enabled()
+                            && self.can_stack(session, rules, tile.pos())? => {}
+                    CaptureMode::Replace | CaptureMode::Ranged => {
+                        if !self.can_capture(session, rules, tile.pos(), tile.pos())? {
+                            continue;
+                        }
+                    }
+                    CaptureMode::Jump | CaptureMode::Custodian => continue,
+                }
+            }
+
+            self.movable.insert(tile.pos());
         }
 
         Ok(())
     }
 
+    /// Collects the positions of the pieces captured by moving to the target.
+    pub fn collect_captured(
+        &self,
+        session: &GameSession,
+        rules: &PieceRules,
+        stacking: StackMode,
+        topology: Topology,
+        target: Pos,
+    ) -> Result<Vec<Pos>, GameError> {
+        let index = &session.placed_pieces;
+
+        let candidates = match rules.capture_mode() {
+            CaptureMode::Replace
+                if stacking.is_enabled() && self.can_stack(session, rules, target)? =>
+            {
+                vec![]
+            }
+            CaptureMode::Replace | CaptureMode::Ranged => vec![target],
+            // Allies are jumped over unharmed.
+            CaptureMode::Jump => jumped_over(self.source, target, topology)
+                .map(|pos| session.wrap.normalize(pos))
+                .filter(|&pos| {
+                    index
+                        .get(pos)
+                        .is_some_and(|placed| !session.teams.same_team(placed.color(), self.color))
+                })
+                .collect(),
+            CaptureMode::Custodian => topology
+                .neighbour_offsets()
+                .iter()
+                .filter_map(|&(dr, dc)| {
+                    let wrap = session.wrap;
+                    let victim = wrap.normalize(Pos::new(target.row() + dr, target.col() + dc));
+                    let anvil =
+                        wrap.normalize(Pos::new(target.row() + 2 * dr, target.col() + 2 * dc));
+
+                    // The victim must be an enemy and the far side must be an ally.
+                    let teams = &session.teams;
+                    let enemy = !teams.same_team(index.get(victim)?.color(), self.color);
+                    let ally = teams.same_team(index.get(anvil)?.color(), self.color);
+
+                    (enemy && ally).then_some(victim)
+                })
+                .collect(),
+        };
+
+        let mut captured = Vec::new();
+
+        for pos in candidates {
+            if index.is_occupied(pos) && self.can_capture(session, rules, target, pos)? {
+                captured.push(pos);
+            }
+        }
+
+        Ok(captured)
+    }
+
+    /// Evaluates whether the piece at `capture` can be captured by moving to `target`.
+    fn can_capture(
+        &self,
+        session: &GameSession,
+        rules: &PieceRules,
+        target: Pos,
+        capture: Pos,
+    ) -> Result<bool, GameError> {
+        let ctx = CaptureContext {
+            session,
+            random_calls: RandomCalls::default(),
+            moving_model: self.model,
+            moving_color: self.color,
+            source_pos: self.source,
+            target_pos: target,
+            capture_pos: capture,
+        };
+
+        rules.can_capture(&ctx)
+    }
+
+    /// Evaluates whether the piece goes on top of the stack at `target` instead of capturing it.
+    fn can_stack(
+        &self,
+        session: &GameSession,
+        rules: &PieceRules,
+        target: Pos,
+    ) -> Result<bool, GameError> {
+        let ctx = CaptureContext {
+            session,
+            random_calls: RandomCalls::default(),
+            moving_model: self.model,
+            moving_color: self.color,
+            source_pos: self.source,
+            target_pos: target,
+            capture_pos: target,
+        };
+
+        rules.can_stack(&ctx)
+    }
+
     /// Returns the set of movable positions.
     pub fn movable_tiles(&self) -> impl Iterator<Item = Pos> {
         self.movable.iter().cloned()
@@ -177,21 +329,62 @@ impl MovingPiece {
     pub fn entities(&self) -> &PieceEntities {
         &self.entities
     }
+
+    /// Returns the pieces carried under the moving piece, ordered from bottom to top.
+    pub fn carried(&self) -> &[PlacedPiece] {
+        &self.carried
+    }
+
+    /// Returns the moving piece as placed at its source position.
+    pub fn to_placed(&self) -> PlacedPiece {
+        PlacedPiece {
+            model: self.model,
+            placed_as: self.placed_as,
+            color: self.color,
+            pos: self.source,
+            entities: self.entities.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Resource)]
+pub struct PromotingPiece {
+    pos: Pos,
+    models: Vec<PieceModel>,
+}
+
+impl PromotingPiece {
+    /// Creates a new promoting piece.
+    pub fn new(pos: Pos, models: Vec<PieceModel>) -> Self {
+        Self { pos, models }
+    }
+
+    /// Returns the position of the promoting piece.
+    pub fn pos(&self) -> Pos {
+        self.pos
+    }
+
+    /// Returns the models the piece can promote to.
+    pub fn models(&self) -> impl Iterator<Item = PieceModel> {
+        self.models.iter().copied()
+    }
 }
 
 #[derive(Debug, Resource)]
 pub struct PlacingPiece {
     model: PieceModel,
     color: PieceColor,
+    source: PieceSource,
     placeable: HashSet<Pos>,
 }
 
 impl PlacingPiece {
     /// Creates a new placing piece.
-    pub fn new(model: PieceModel, color: PieceColor) -> Self {
+    pub fn new(model: PieceModel, color: PieceColor, source: PieceSource) -> Self {
         Self {
             model,
             color,
+            source,
             placeable: HashSet::new(),
         }
     }
@@ -206,9 +399,11 @@ impl PlacingPiece {
         for tile in tile_query {
             let ctx = PlacementContext {
                 session,
+                random_calls: RandomCalls::default(),
                 to_place_model: self.model,
                 to_place_color: self.color,
                 to_place_pos: tile.pos(),
+                to_place_is_drop: self.source == PieceSource::Hand,
             };
 
             if rules.can_place(&ctx)? {
@@ -238,6 +433,11 @@ impl PlacingPiece {
     pub fn color(&self) -> PieceColor {
         self.color
     }
+
+    /// Returns where the piece is taken from.
+    pub fn source(&self) -> PieceSource {
+        self.source
+    }
 }
 
 #[derive(Debug, Component)]
@@ -253,28 +453,32 @@ pub fn place_new_piece(
     placed_pieces: &mut PlacedPieceIndex,
     model: PieceModel,
     color: PieceColor,
+    source: PieceSource,
     pos: Pos,
 ) -> Result<(), GameError> {
     fn on_piece_pressed(on_press: On<Pointer<Press>>, mut msg: MessageWriter<PiecePress>) {
         msg.write(PiecePress(on_press.event_target(), on_press.button));
     }
 
-    let Entry::Vacant(entry) = placed_pieces.entry(pos) else {
+    // The new piece goes on top of the stack.
+    let level = placed_pieces.height(pos);
+
+    if level > 0 && !rules.stacking().is_enabled() {
         // Try to spawn duplicate piece at one position.
         return Err(GameError::DuplicatePiece(pos));
-    };
+    }
 
-    // Decrease the piece stock
+    // Decrease the piece stock or hand
     players
         .get_by_color_mut(color)
         .piece_mut(model)
-        .try_take_stock()?;
+        .try_take(source)?;
 
     let (mesh, local_transform) = assets.meshes.piece.get(model);
 
     // Animation
     let (transform, movement_sequence, scale_sequence) = {
-        let end = pos_translation(pos, rules);
+        let end = pos_translation(pos, level, rules);
         let mut start = end;
 
         let movement_sequence = {
@@ -345,7 +549,7 @@ pub fn place_new_piece(
     let base_mesh = commands
         .spawn((
             Mesh3d(mesh.clone()),
-            MeshMaterial3d(assets.materials.piece.get(color).clone()),
+            MeshMaterial3d(assets.materials.piece.get_for_piece(color, model).clone()),
             local_transform.clone(),
             GlobalTransform::default(),
         ))
@@ -369,7 +573,7 @@ pub fn place_new_piece(
         .add_children(&[base_mesh, highlight]);
 
     // Add to placed piece index
-    entry.insert(PlacedPiece::new(
+    placed_pieces.push(PlacedPiece::new(
         model,
         color,
         pos,
@@ -379,19 +583,262 @@ pub fn place_new_piece(
     Ok(())
 }
 
-/// Despawns a piece at the specified position.
+/// Promotes the piece at the specified position to the given model.
+///
+/// Only the model and meshes change, the stock is left untouched.
+pub fn promote_piece(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    placed_pieces: &mut PlacedPieceIndex,
+    pos: Pos,
+    model: PieceModel,
+) -> Result<(), GameError> {
+    let Some(placed) = placed_pieces.set_model(pos, model) else {
+        return Err(GameError::NoPieceAtPos(pos));
+    };
+
+    let (mesh, local_transform) = assets.meshes.piece.get(model);
+
+    for entity in [placed.entities().base_mesh(), placed.entities().highlight()] {
+        commands
+            .entity(entity)
+            .insert((Mesh3d(mesh.clone()), *local_transform));
+    }
+
+    // The new kind may have its own texture.
+    commands
+        .entity(placed.entities().base_mesh())
+        .insert(MeshMaterial3d(
+            assets
+                .materials
+                .piece
+                .get_for_piece(placed.color(), model)
+                .clone(),
+        ));
+
+    Ok(())
+}
+
+/// Shows, hides or disguises every placed piece for the viewing player.
+///
+/// Pieces with a hidden model keep their color but use a generic mesh and no texture.
+/// Without a viewer, every piece is revealed.
+pub fn update_piece_visibility(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    rules: &CheckedGameRules,
+    session: &GameSession,
+    viewer: Option<PieceColor>,
+) -> Result<(), GameError> {
+    for placed in session.placed_pieces.iter() {
+        let (visible, model_visible) = match viewer {
+            Some(viewer_color) => {
+                let piece_rules = rules.get_piece(placed.model())?;
+
+                let ctx = VisibilityContext {
+                    session,
+                    random_calls: RandomCalls::default(),
+                    viewer_color,
+                    viewed_pos: placed.pos(),
+                };
+
+                (
+                    piece_rules.is_visible(&ctx)?,
+                    piece_rules.is_model_visible(&ctx)?,
+                )
+            }
+            None => (true, true),
+        };
+
+        let visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        commands.entity(placed.entities().root()).insert(visibility);
+
+        let (mesh, local_transform) = if model_visible {
+            assets.meshes.piece.get(placed.model())
+        } else {
+            assets.meshes.piece.hidden()
+        };
+
+        for entity in [placed.entities().base_mesh(), placed.entities().highlight()] {
+            commands
+                .entity(entity)
+                .insert((Mesh3d(mesh.clone()), *local_transform));
+        }
+
+        // A texture would give a disguised piece away, so it only keeps its color.
+        let material = if model_visible {
+            assets
+                .materials
+                .piece
+                .get_for_piece(placed.color(), placed.model())
+        } else {
+            assets.materials.piece.get(placed.color())
+        };
+
+        commands
+            .entity(placed.entities().base_mesh())
+            .insert(MeshMaterial3d(material.clone()));
+    }
+
+    Ok(())
+}
+
+/// Returns the positions strictly between `from` and `to`.
+///
+/// Empty unless both positions share a row, a column or a diagonal,
+/// or one of the three axes on a hex board.
+fn jumped_over(from: Pos, to: Pos, topology: Topology) -> impl Iterator<Item = Pos> {
+    let dr = to.row() - from.row();
+    let dc = to.col() - from.col();
+
+    let straight = if topology.is_hex() {
+        dr == 0 || dc == 0 || dr == -dc
+    } else {
+        dr == 0 || dc == 0 || dr.abs() == dc.abs()
+    };
+
+    let steps = if straight { dr.abs().max(dc.abs()) } else { 0 };
+
+    (1..steps).map(move |i| Pos::new(from.row() + i * dr.signum(), from.col() + i * dc.signum()))
+}
+
+/// Despawns the top piece at the specified position.
+///
+/// The captured piece is then handled according to the capture policy.
+/// Returns if a piece was captured.
 pub fn capture_piece(
     commands: &mut Commands,
+    rules: &CheckedGameRules,
     placed_piece_index: &mut PlacedPieceIndex,
     players: &mut Players,
+    capturer: PieceColor,
     pos: Pos,
-) {
-    if let Some(placed) = placed_piece_index.remove(&pos) {
-        players
-            .get_by_color_mut(placed.color())
-            .piece_mut(placed.model())
-            .record_capture();
-
-        commands.entity(placed.entities().root()).despawn();
+) -> bool {
+    let Some(placed) = placed_piece_index.pop(pos) else {
+        return false;
+    };
+
+    players.capture(rules.capture_policy(), &placed, capturer);
+
+    commands.entity(placed.entities().root()).despawn();
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::playing::session::tests::{placed, rules_with, session};
+
+    /// Rules with a `Mover` kind, whose extra fields are given, and a `Light` team of White and Gray.
+    fn rules(mover: &str, stacking: &str) -> CheckedGameRules {
+        rules_with(
+            &format!(
+                "Stone: (count: Infinite, movement: False, placement: True),
+                Mover: (count: Infinite, movement: True, placement: True, {mover})"
+            ),
+            &["White", "Black", "Gray"],
+            &format!(r#"teams: {{ "Light": [White, Gray] }}, stacking: {stacking},"#),
+        )
+    }
+
+    /// Moves a white `Mover` from `source` to `target` among stones, returns the captured positions.
+    fn captured(
+        rules: &CheckedGameRules,
+        stones: &[(&str, i64, i64)],
+        source: (i64, i64),
+        target: (i64, i64),
+    ) -> Vec<Pos> {
+        let mut session = session(rules);
+
+        for &(color, row, col) in stones {
+            session.placed_pieces.push(placed("Stone", color, row, col));
+        }
+
+        let model = PieceModel::new("Mover");
+        let mover = placed("Mover", "White", source.0, source.1);
+        let moving = MovingPiece::new(&mover, vec![]);
+
+        let mut captured = moving
+            .collect_captured(
+                &session,
+                rules.get_piece(model).unwrap(),
+                rules.stacking(),
+                rules.board_topology(),
+                Pos::new(target.0, target.1),
+            )
+            .unwrap();
+
+        captured.sort();
+        captured
+    }
+
+    #[test]
+    fn stacking_pieces_go_on_top() {
+        let rules = rules("", "TopPiece");
+
+        assert!(captured(&rules, &[("Black", 0, 1)], (0, 0), (0, 1)).is_empty());
+    }
+
+    #[test]
+    fn stack_rule_lets_stacking_pieces_capture() {
+        let rules = rules(
+            "stack: Some(SameTeam(ColorAtPos(CaptureRow, CaptureCol), MovingColor))",
+            "TopPiece",
+        );
+
+        assert!(captured(&rules, &[("White", 0, 1)], (0, 0), (0, 1)).is_empty());
+        assert_eq!(
+            captured(&rules, &[("Black", 0, 1)], (0, 0), (0, 1)),
+            [Pos::new(0, 1)]
+        );
+    }
+
+    #[test]
+    fn custodian_captures_enemies_between_allies() {
+        let rules = rules("capture_mode: Custodian", "Disabled");
+        let stones = [
+            ("Black", 2, 3),
+            ("Gray", 2, 4),
+            ("Gray", 2, 1),
+            ("White", 2, 0),
+        ];
+
+        assert_eq!(captured(&rules, &stones, (0, 2), (2, 2)), [Pos::new(2, 3)]);
+    }
+
+    #[test]
+    fn jump_captures_jumped_over_enemies() {
+        let rules = rules("capture_mode: Jump", "Disabled");
+        let stones = [("Black", 1, 1), ("Gray", 2, 2), ("Black", 3, 3)];
+
+        assert_eq!(
+            captured(&rules, &stones, (0, 0), (4, 4)),
+            [Pos::new(1, 1), Pos::new(3, 3)]
+        );
+    }
+
+    #[test]
+    fn ranged_captures_the_target_only() {
+        let rules = rules("capture_mode: Ranged", "Disabled");
+        let stones = [("Black", 0, 2), ("Black", 0, 1)];
+
+        assert_eq!(captured(&rules, &stones, (0, 0), (0, 2)), [Pos::new(0, 2)]);
+    }
+
+    #[test]
+    fn capture_condition_filters_candidates() {
+        let rules = rules(
+            "capture_mode: Jump, capture: Some(Equal(CaptureRow, Const(3)))",
+            "Disabled",
+        );
+        let stones = [("Black", 1, 1), ("Black", 3, 3)];
+
+        assert_eq!(captured(&rules, &stones, (0, 0), (4, 4)), [Pos::new(3, 3)]);
     }
 }
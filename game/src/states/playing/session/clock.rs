@@ -0,0 +1,413 @@
+use crate::locale::{Strings, fill};
+use bevy::time::{Real, Time};
+use indexmap::IndexMap;
+use rulery::{CheckedGameRules, clock::TimeControl, piece::PieceColor};
+use std::time::Duration;
+
+/// A monotonic source of the current time, so clocks can be driven by a fake one.
+pub trait TimeSource {
+    /// Returns the time elapsed since an arbitrary fixed point.
+    fn now(&self) -> Duration;
+}
+
+impl TimeSource for Time<Real> {
+    fn now(&self) -> Duration {
+        self.elapsed()
+    }
+}
+
+/// The time a player has left.
+#[derive(Debug, Clone, Copy)]
+struct PlayerClock {
+    /// Main time left.
+    main: Duration,
+
+    /// Byo-yomi periods left, including the current one.
+    periods: u32,
+
+    /// Time left in the current byo-yomi period.
+    period_left: Duration,
+}
+
+impl PlayerClock {
+    /// Returns if the player has no time left.
+    fn is_flagged(&self) -> bool {
+        self.main.is_zero() && self.periods == 0
+    }
+
+    /// Charges elapsed time, first to the main time, then to the byo-yomi periods.
+    fn consume(&mut self, elapsed: Duration, period: Duration) {
+        let mut over = elapsed.saturating_sub(self.main);
+        self.main = self.main.saturating_sub(elapsed);
+
+        while !over.is_zero() && self.periods > 0 {
+            if self.period_left > over {
+                self.period_left -= over;
+                over = Duration::ZERO;
+            } else {
+                over -= self.period_left;
+                self.periods -= 1;
+                self.period_left = if self.periods > 0 {
+                    period
+                } else {
+                    Duration::ZERO
+                };
+            }
+        }
+    }
+
+    /// Returns the time left before the flag falls.
+    fn remaining(&self, period: Duration) -> Duration {
+        match self.periods {
+            0 => self.main,
+            n => self.main + self.period_left + period * (n - 1),
+        }
+    }
+}
+
+/// Clocks of every player.
+///
+/// Only the running clock is charged, with the time between calls read from a [`TimeSource`].
+#[derive(Debug, Default)]
+pub struct Clocks {
+    /// Time added after each turn.
+    increment: Duration,
+
+    /// Length of each byo-yomi period.
+    period: Duration,
+
+    /// Clock of each player, empty for untimed games.
+    clocks: IndexMap<PieceColor, PlayerClock>,
+
+    /// The running clock and when it was last charged.
+    running: Option<(PieceColor, Duration)>,
+}
+
+impl Clocks {
+    /// Creates clocks for every player, if the rules are timed.
+    pub fn new(rules: &CheckedGameRules) -> Self {
+        let Some(control) = rules.time_control() else {
+            return Self::default();
+        };
+
+        Self::with_time_control(control, rules.players().map(|(color, _)| color))
+    }
+
+    /// Creates clocks for the given players.
+    pub fn with_time_control(
+        control: &TimeControl,
+        players: impl IntoIterator<Item = PieceColor>,
+    ) -> Self {
+        let (periods, period) = match control.byo_yomi() {
+            Some(byo_yomi) => (
+                byo_yomi.periods(),
+                Duration::from_millis(byo_yomi.period_millis()),
+            ),
+            None => (0, Duration::ZERO),
+        };
+
+        let clock = PlayerClock {
+            main: Duration::from_millis(control.initial_millis()),
+            periods,
+            period_left: period,
+        };
+
+        Self {
+            increment: Duration::from_millis(control.increment_millis()),
+            period,
+            clocks: players.into_iter().map(|color| (color, clock)).collect(),
+            running: None,
+        }
+    }
+
+    /// Returns if the game is timed.
+    pub fn is_enabled(&self) -> bool {
+        !self.clocks.is_empty()
+    }
+
+    /// Starts the player's clock, unless it is already running.
+    ///
+    /// The turn of another running clock is finished first.
+    pub fn start(&mut self, color: PieceColor, time: &impl TimeSource) {
+        if !self.is_enabled() || self.running.is_some_and(|(running, _)| running == color) {
+            return;
+        }
+
+        self.finish_turn(time);
+        self.running = Some((color, time.now()));
+    }
+
+    /// Charges the time since the last call to the running clock.
+    ///
+    /// Returns if the flag of the running clock has just fallen.
+    pub fn tick(&mut self, time: &impl TimeSource) -> bool {
+        let Some((color, last)) = self.running else {
+            return false;
+        };
+
+        let now = time.now();
+        self.running = Some((color, now));
+
+        let Some(clock) = self.clocks.get_mut(&color) else {
+            return false;
+        };
+
+        let was_flagged = clock.is_flagged();
+        clock.consume(now.saturating_sub(last), self.period);
+
+        !was_flagged && clock.is_flagged()
+    }
+
+    /// Stops the running clock at the end of a turn.
+    ///
+    /// Adds the increment, or restores the current byo-yomi period, unless the flag has fallen.
+    pub fn finish_turn(&mut self, time: &impl TimeSource) {
+        self.tick(time);
+
+        let Some((color, _)) = self.running.take() else {
+            return;
+        };
+
+        let Some(clock) = self.clocks.get_mut(&color) else {
+            return;
+        };
+
+        if clock.is_flagged() {
+            return;
+        }
+
+        if clock.main.is_zero() {
+            clock.period_left = self.period;
+        } else {
+            clock.main += self.increment;
+        }
+    }
+
+    /// Stops the running clock without ending the turn.
+    pub fn pause(&mut self, time: &impl TimeSource) {
+        self.tick(time);
+        self.running = None;
+    }
+
+    /// Returns the time the player has left, `None` for untimed games.
+    pub fn remaining(&self, color: PieceColor) -> Option<Duration> {
+        self.clocks
+            .get(&color)
+            .map(|clock| clock.remaining(self.period))
+    }
+
+    /// Returns the clocks as text, e.g. `White 04:59 · Black 05:00 (3×30s)`.
+    pub fn message(&self, rules: &CheckedGameRules, strings: &Strings) -> String {
+        let clocks: Vec<String> = self
+            .clocks
+            .iter()
+            .map(|(color, clock)| {
+                let secs = clock.remaining(self.period).as_secs();
+                let mut text = format!(
+                    "{} {:02}:{:02}",
+                    rules.color_name(*color),
+                    secs / 60,
+                    secs % 60
+                );
+
+                if clock.main.is_zero() && clock.periods > 0 {
+                    text = fill(
+                        strings.byo_yomi,
+                        &[
+                            ("clock", &text),
+                            ("periods", &clock.periods),
+                            ("secs", &self.period.as_secs()),
+                        ],
+                    );
+                }
+
+                text
+            })
+            .collect();
+
+        clocks.join(strings.clock_separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{locale::Locale, states::playing::session::tests::rules};
+    use rulery::clock::ByoYomi;
+    use std::cell::Cell;
+
+    /// A clock on the wall that only moves when told to.
+    #[derive(Default)]
+    struct FakeTime(Cell<Duration>);
+
+    impl FakeTime {
+        fn advance(&self, millis: u64) {
+            self.0.set(self.0.get() + Duration::from_millis(millis));
+        }
+    }
+
+    impl TimeSource for FakeTime {
+        fn now(&self) -> Duration {
+            self.0.get()
+        }
+    }
+
+    fn white() -> PieceColor {
+        PieceColor::new("White")
+    }
+
+    fn black() -> PieceColor {
+        PieceColor::new("Black")
+    }
+
+    fn clocks(control: TimeControl) -> Clocks {
+        Clocks::with_time_control(&control, [white(), black()])
+    }
+
+    fn millis(clocks: &Clocks, color: PieceColor) -> u128 {
+        clocks.remaining(color).unwrap().as_millis()
+    }
+
+    #[test]
+    fn only_the_running_clock_is_charged() {
+        let time = FakeTime::default();
+        let mut clocks = clocks(TimeControl::new(10_000, 0, None));
+
+        clocks.start(white(), &time);
+        time.advance(3_000);
+        assert!(!clocks.tick(&time));
+
+        assert_eq!(millis(&clocks, white()), 7_000);
+        assert_eq!(millis(&clocks, black()), 10_000);
+
+        clocks.start(black(), &time);
+        time.advance(1_000);
+        clocks.tick(&time);
+
+        assert_eq!(millis(&clocks, white()), 7_000);
+        assert_eq!(millis(&clocks, black()), 9_000);
+    }
+
+    #[test]
+    fn byo_yomi_is_used_after_main_time() {
+        let mut clock = PlayerClock {
+            main: Duration::from_secs(10),
+            periods: 3,
+            period_left: Duration::from_secs(5),
+        };
+        let period = Duration::from_secs(5);
+
+        assert_eq!(clock.remaining(period), Duration::from_secs(25));
+
+        // Overruns the main time and one period, then starts on the second.
+        clock.consume(Duration::from_secs(17), period);
+        assert!(clock.main.is_zero());
+        assert_eq!(clock.periods, 2);
+        assert_eq!(clock.period_left, Duration::from_secs(3));
+        assert_eq!(clock.remaining(period), Duration::from_secs(8));
+
+        clock.consume(Duration::from_secs(8), period);
+        assert!(clock.is_flagged());
+        assert!(clock.remaining(period).is_zero());
+    }
+
+    #[test]
+    fn flag_falls_once() {
+        let time = FakeTime::default();
+        let mut clocks = clocks(TimeControl::new(1_000, 0, Some(ByoYomi::new(1, 500))));
+
+        clocks.start(white(), &time);
+        time.advance(1_200);
+        assert!(!clocks.tick(&time));
+        assert_eq!(millis(&clocks, white()), 300);
+
+        time.advance(300);
+        assert!(clocks.tick(&time));
+        assert_eq!(millis(&clocks, white()), 0);
+
+        time.advance(1_000);
+        assert!(!clocks.tick(&time));
+    }
+
+    #[test]
+    fn finishing_a_turn_adds_the_increment() {
+        let time = FakeTime::default();
+        let mut clocks = clocks(TimeControl::new(10_000, 2_000, None));
+
+        clocks.start(white(), &time);
+        time.advance(3_000);
+        clocks.finish_turn(&time);
+        assert_eq!(millis(&clocks, white()), 9_000);
+
+        // The clock is stopped until it is started again.
+        time.advance(5_000);
+        assert!(!clocks.tick(&time));
+        assert_eq!(millis(&clocks, white()), 9_000);
+
+        // Starting the next player finishes the running turn, with a single increment.
+        clocks.start(white(), &time);
+        time.advance(1_000);
+        clocks.start(black(), &time);
+        assert_eq!(millis(&clocks, white()), 10_000);
+        assert_eq!(millis(&clocks, black()), 10_000);
+    }
+
+    #[test]
+    fn finishing_a_turn_restores_the_byo_yomi_period() {
+        let time = FakeTime::default();
+        let mut clocks = clocks(TimeControl::new(1_000, 2_000, Some(ByoYomi::new(2, 500))));
+
+        clocks.start(white(), &time);
+        time.advance(1_300);
+        clocks.finish_turn(&time);
+
+        // No increment once the main time is gone.
+        assert_eq!(millis(&clocks, white()), 1_000);
+    }
+
+    #[test]
+    fn pausing_stops_the_clocks() {
+        let time = FakeTime::default();
+        let mut clocks = clocks(TimeControl::new(10_000, 2_000, None));
+
+        clocks.start(white(), &time);
+        time.advance(4_000);
+        clocks.pause(&time);
+        assert_eq!(millis(&clocks, white()), 6_000);
+
+        // Time after the game is over is charged to no one, and earns no increment.
+        time.advance(60_000);
+        assert!(!clocks.tick(&time));
+        clocks.finish_turn(&time);
+        assert_eq!(millis(&clocks, white()), 6_000);
+        assert_eq!(millis(&clocks, black()), 10_000);
+    }
+
+    #[test]
+    fn untimed_games_have_no_clocks() {
+        let time = FakeTime::default();
+        let mut clocks = Clocks::default();
+
+        clocks.start(white(), &time);
+        time.advance(1_000);
+
+        assert!(!clocks.is_enabled());
+        assert!(!clocks.tick(&time));
+        assert_eq!(clocks.remaining(white()), None);
+    }
+
+    #[test]
+    fn message_follows_the_locale() {
+        let rules = rules();
+        let clocks = clocks(TimeControl::new(0, 0, Some(ByoYomi::new(3, 30_000))));
+
+        assert_eq!(
+            clocks.message(&rules, Locale::English.strings()),
+            "White 01:30 (3×30s) · Black 01:30 (3×30s)"
+        );
+        assert_eq!(
+            clocks.message(&rules, Locale::Japanese.strings()),
+            "White 01:30（秒読み 3×30秒） · Black 01:30（秒読み 3×30秒）"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use rulery::piece::PieceModel;
+use std::collections::HashMap;
+
+/// Turn numbers of the latest actions of each kind, for rules such as the 50-move rule.
+///
+/// Before an action first happens, the game start counts as turn 0.
+#[derive(Debug, Default)]
+pub struct TurnCounters {
+    /// The turn of the latest capture.
+    last_capture: i64,
+
+    /// The turn of the latest placement.
+    last_placement: i64,
+
+    /// The turn each model last moved in.
+    last_moves: HashMap<PieceModel, i64>,
+}
+
+impl TurnCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a capture in the given turn.
+    pub fn record_capture(&mut self, turn_number: i64) {
+        self.last_capture = turn_number;
+    }
+
+    /// Records a placement in the given turn.
+    pub fn record_placement(&mut self, turn_number: i64) {
+        self.last_placement = turn_number;
+    }
+
+    /// Records a move of the model in the given turn.
+    pub fn record_move(&mut self, model: PieceModel, turn_number: i64) {
+        self.last_moves.insert(model, turn_number);
+    }
+
+    /// Returns the number of turns since the latest capture.
+    pub fn turns_since_capture(&self, turn_number: i64) -> i64 {
+        turn_number - self.last_capture
+    }
+
+    /// Returns the number of turns since the latest placement.
+    pub fn turns_since_placement(&self, turn_number: i64) -> i64 {
+        turn_number - self.last_placement
+    }
+
+    /// Returns the number of turns since the model last moved.
+    pub fn turns_since_move_of(&self, model: PieceModel, turn_number: i64) -> i64 {
+        turn_number - self.last_moves.get(&model).copied().unwrap_or_default()
+    }
+}
@@ -1,20 +1,136 @@
-use crate::states::playing::{
-    piece::PlacedPiece,
-    session::{player::Players, turn::TurnController},
-    tile::TileEntities,
+use crate::{
+    GameError,
+    expr_contexts::win_or_lose::WinOrLoseContext,
+    states::playing::{
+        piece::PlacedPiece,
+        session::{
+            clock::Clocks, counters::TurnCounters, player::Players, random::RandomCalls,
+            team::Teams, turn::TurnController, zobrist::PositionHistory,
+        },
+        tile::TileEntities,
+    },
 };
 use bevy::prelude::*;
-use rulery::pos::Pos;
+use rulery::{
+    CheckedGameRules,
+    board::BoardWrap,
+    piece::{PieceColor, PieceModel},
+    pos::Pos,
+};
 use std::collections::HashMap;
 
+pub mod clock;
+pub mod counters;
 pub mod player;
+pub mod random;
+pub mod team;
 pub mod turn;
+pub mod zobrist;
 
 /// Indexes for tiles.
 pub type TileIndex = HashMap<Pos, TileEntities>;
 
 /// Indexes for placed pieces.
-pub type PlacedPieceIndex = HashMap<Pos, PlacedPiece>;
+///
+/// Each tile holds a stack of pieces, ordered from bottom to top.
+/// The Zobrist hash of the pieces is kept up to date on every change.
+#[derive(Debug, Default)]
+pub struct PlacedPieceIndex {
+    stacks: HashMap<Pos, Vec<PlacedPiece>>,
+    hash: u64,
+}
+
+impl PlacedPieceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the top piece at the position.
+    pub fn get(&self, pos: Pos) -> Option<&PlacedPiece> {
+        self.stacks.get(&pos).and_then(|stack| stack.last())
+    }
+
+    /// Changes the model of the top piece at the position.
+    pub fn set_model(&mut self, pos: Pos, model: PieceModel) -> Option<&PlacedPiece> {
+        let stack = self.stacks.get_mut(&pos)?;
+        let level = stack.len() - 1;
+        let placed = stack.last_mut()?;
+
+        self.hash ^= zobrist::piece_key(placed.model(), placed.color(), pos, level);
+        self.hash ^= zobrist::piece_key(model, placed.color(), pos, level);
+
+        placed.set_model(model);
+
+        Some(placed)
+    }
+
+    /// Returns the piece at a depth of the stack, depth 0 being the top piece.
+    pub fn get_at_depth(&self, pos: Pos, depth: i64) -> Option<&PlacedPiece> {
+        let stack = self.stacks.get(&pos)?;
+        let depth = usize::try_from(depth).ok()?;
+
+        stack.iter().rev().nth(depth)
+    }
+
+    /// Returns the number of pieces stacked at the position.
+    pub fn height(&self, pos: Pos) -> usize {
+        self.stacks.get(&pos).map_or(0, Vec::len)
+    }
+
+    /// Returns if any piece is at the position.
+    pub fn is_occupied(&self, pos: Pos) -> bool {
+        self.height(pos) > 0
+    }
+
+    /// Returns an iterator over the top piece of every stack.
+    pub fn tops(&self) -> impl Iterator<Item = &PlacedPiece> {
+        self.stacks.values().filter_map(|stack| stack.last())
+    }
+
+    /// Returns an iterator over all placed pieces, including those under the top.
+    pub fn iter(&self) -> impl Iterator<Item = &PlacedPiece> {
+        self.stacks.values().flatten()
+    }
+
+    /// Puts a piece on top of the stack at its position.
+    pub fn push(&mut self, placed: PlacedPiece) {
+        let stack = self.stacks.entry(placed.pos()).or_default();
+
+        self.hash ^= zobrist::piece_key(placed.model(), placed.color(), placed.pos(), stack.len());
+
+        stack.push(placed);
+    }
+
+    /// Removes the top piece at the position.
+    pub fn pop(&mut self, pos: Pos) -> Option<PlacedPiece> {
+        let stack = self.stacks.get_mut(&pos)?;
+        let placed = stack.pop()?;
+
+        self.hash ^= zobrist::piece_key(placed.model(), placed.color(), pos, stack.len());
+
+        if stack.is_empty() {
+            self.stacks.remove(&pos);
+        }
+
+        Some(placed)
+    }
+
+    /// Removes the whole stack at the position, ordered from bottom to top.
+    pub fn take(&mut self, pos: Pos) -> Vec<PlacedPiece> {
+        let stack = self.stacks.remove(&pos).unwrap_or_default();
+
+        for (level, placed) in stack.iter().enumerate() {
+            self.hash ^= zobrist::piece_key(placed.model(), placed.color(), pos, level);
+        }
+
+        stack
+    }
+
+    /// Returns the Zobrist hash of all placed pieces.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
 
 #[derive(Debug, Resource)]
 pub struct GameSession {
@@ -22,6 +138,249 @@ pub struct GameSession {
     pub tiles: TileIndex,
     pub placed_pieces: PlacedPieceIndex,
     pub players: Players,
+    pub teams: Teams,
     pub turn: TurnController,
     pub last_action: Option<Pos>,
+    pub history: PositionHistory,
+    pub counters: TurnCounters,
+    pub clocks: Clocks,
+
+    /// Lengths of the wrapped board axes, positions in queries are normalised with it.
+    pub wrap: BoardWrap,
+}
+
+impl GameSession {
+    /// Returns the Zobrist hash of the position: the placed pieces, every player's stock and hand,
+    /// and the player to act next with the actions they have left.
+    ///
+    /// Also suitable as a key for transposition tables.
+    pub fn position_hash(&self, to_move: PieceColor, actions_left: usize) -> u64 {
+        let mut hash = self.placed_pieces.hash();
+
+        for (color, player) in self.players.iter() {
+            for (model, piece) in player.pieces() {
+                hash ^= zobrist::stock_key(color, model, piece.stock(), piece.hand());
+            }
+        }
+
+        hash ^ zobrist::to_move_key(to_move, actions_left)
+    }
+
+    /// Evaluates the score of every player.
+    pub fn update_scores(&mut self, rules: &CheckedGameRules) -> Result<(), GameError> {
+        let scores = self
+            .players
+            .iter()
+            .map(|(color, _)| {
+                let ctx = WinOrLoseContext {
+                    session: self,
+                    random_calls: RandomCalls::default(),
+                };
+
+                Ok((color, rules.get_player(color)?.evaluate_score(&ctx)?))
+            })
+            .collect::<Result<Vec<_>, GameError>>()?;
+
+        for (color, score) in scores {
+            self.players.get_by_color_mut(color).set_score(score);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::states::playing::{
+        piece::PieceEntities,
+        session::{player::PieceSource, random::SessionRng},
+    };
+    use rulery::{CheckedGameRules, UncheckedGameRules, format::Format};
+
+    /// Rules on a 5×5 board with the given pieces and players, and further top-level fields.
+    pub(crate) fn rules_with(pieces: &str, players: &[&str], fields: &str) -> CheckedGameRules {
+        let players: String = players
+            .iter()
+            .map(|color| format!("{color}: (lose_condition: False, win_condition: False), "))
+            .collect();
+
+        let ron = format!(
+            r#"(
+    name: "Test",
+    board: (rows: 5, cols: 5),
+    pieces: {{ {pieces} }},
+    players: {{ {players} }},
+    initial_layout: [],
+    game_over_condition: False,
+    {fields}
+)"#
+        );
+
+        UncheckedGameRules::parse(&ron, Format::Ron)
+            .unwrap()
+            .check()
+            .unwrap()
+    }
+
+    /// Rules with stones for White and Black.
+    pub(crate) fn rules() -> CheckedGameRules {
+        rules_with(
+            "Stone: (count: Finite(2), movement: True, placement: True)",
+            &["White", "Black"],
+            "",
+        )
+    }
+
+    /// Creates a session without entities, for the given rules.
+    pub(crate) fn session(rules: &CheckedGameRules) -> GameSession {
+        let players = Players::new(rules);
+        let turn = TurnController::new(rules.turn_schedule(), players.num(), SessionRng::new(0));
+
+        GameSession {
+            board: Entity::PLACEHOLDER,
+            tiles: TileIndex::new(),
+            placed_pieces: PlacedPieceIndex::new(),
+            players,
+            teams: Teams::new(rules),
+            turn,
+            last_action: None,
+            history: PositionHistory::new(),
+            counters: TurnCounters::new(),
+            clocks: Clocks::new(rules),
+            wrap: rules.board_wrap_lengths(),
+        }
+    }
+
+    /// Creates a piece without entities.
+    pub(crate) fn placed(model: &str, color: &str, row: i64, col: i64) -> PlacedPiece {
+        let entities = PieceEntities::new(
+            Entity::PLACEHOLDER,
+            Entity::PLACEHOLDER,
+            Entity::PLACEHOLDER,
+        );
+
+        PlacedPiece::new(
+            PieceModel::new(model),
+            PieceColor::new(color),
+            Pos::new(row, col),
+            entities,
+        )
+    }
+
+    fn stone(color: &str, row: i64, col: i64) -> PlacedPiece {
+        placed("Stone", color, row, col)
+    }
+
+    /// Moves the top piece and records the position, as at the end of an action.
+    fn play(
+        session: &mut GameSession,
+        color: &str,
+        from: (i64, i64),
+        to: (i64, i64),
+        next: &str,
+    ) -> i64 {
+        session.placed_pieces.pop(Pos::new(from.0, from.1)).unwrap();
+        session.placed_pieces.push(stone(color, to.0, to.1));
+
+        let hash = session.position_hash(PieceColor::new(next), 1);
+        session.history.record(hash);
+        session.history.repetition_count()
+    }
+
+    #[test]
+    fn repeated_positions_are_counted() {
+        let rules = rules();
+        let mut session = session(&rules);
+
+        session.placed_pieces.push(stone("White", 0, 0));
+        session.placed_pieces.push(stone("Black", 2, 2));
+
+        let hash = session.position_hash(PieceColor::new("White"), 1);
+        session.history.record(hash);
+        assert_eq!(session.history.repetition_count(), 1);
+
+        let mut counts = Vec::new();
+
+        for _ in 0..3 {
+            counts.push(play(&mut session, "White", (0, 0), (0, 1), "Black"));
+            counts.push(play(&mut session, "Black", (2, 2), (2, 1), "White"));
+            counts.push(play(&mut session, "White", (0, 1), (0, 0), "Black"));
+            counts.push(play(&mut session, "Black", (2, 1), (2, 2), "White"));
+        }
+
+        // The starting position repeats after every four actions.
+        assert_eq!(counts, [1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4]);
+    }
+
+    #[test]
+    fn actions_left_are_part_of_the_position() {
+        let rules = rules();
+        let mut session = session(&rules);
+        let white = PieceColor::new("White");
+        let black = PieceColor::new("Black");
+
+        session.placed_pieces.push(stone("White", 0, 0));
+
+        // Mid-turn and end-of-turn positions on the same board differ.
+        let mid_turn = session.position_hash(white, 1);
+
+        assert_ne!(mid_turn, session.position_hash(white, 2));
+        assert_ne!(mid_turn, session.position_hash(black, 1));
+    }
+
+    #[test]
+    fn stock_and_hand_are_part_of_the_position() {
+        let rules = rules();
+        let mut session = session(&rules);
+        let white = PieceColor::new("White");
+        let black = PieceColor::new("Black");
+        let model = PieceModel::new("Stone");
+
+        session.placed_pieces.push(stone("White", 1, 1));
+
+        let start = session.position_hash(white, 1);
+        session.history.record(start);
+
+        // Same board, one stone fewer in stock.
+        session
+            .players
+            .get_by_color_mut(white)
+            .piece_mut(model)
+            .try_take(PieceSource::Stock)
+            .unwrap();
+
+        let taken = session.position_hash(white, 1);
+        assert_ne!(taken, start);
+
+        session.history.record(taken);
+        assert_eq!(session.history.repetition_count(), 1);
+
+        // Same board, one stone in hand.
+        let player = session.players.get_by_color_mut(black);
+        player.piece_mut(model).add_to_hand();
+
+        let in_hand = session.position_hash(white, 1);
+        assert_ne!(in_hand, taken);
+
+        session.history.record(in_hand);
+        assert_eq!(session.history.repetition_count(), 1);
+
+        // Emptying the hand again repeats the position with one stone fewer in stock.
+        let player = session.players.get_by_color_mut(black);
+        player.piece_mut(model).try_take(PieceSource::Hand).unwrap();
+
+        session.history.record(session.position_hash(white, 1));
+        assert_eq!(session.history.repetition_count(), 2);
+
+        // Returning the stone to stock repeats the starting position.
+        session
+            .players
+            .get_by_color_mut(white)
+            .piece_mut(model)
+            .return_to_stock();
+
+        session.history.record(session.position_hash(white, 1));
+        assert_eq!(session.history.repetition_count(), 2);
+    }
 }
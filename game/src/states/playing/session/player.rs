@@ -1,35 +1,86 @@
-use crate::GameError;
+use crate::{GameError, locale::Strings, states::playing::piece::PlacedPiece};
 use indexmap::IndexMap;
 use rulery::{
-    CheckedGameRules,
+    CheckedGameRules, RulesError,
+    capture::CapturePolicy,
     count::Count,
     piece::{PieceColor, PieceModel},
-    player::PlayerState,
+    player::{PlayerState, TieBreak},
 };
 
+/// Where a piece to place is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceSource {
+    /// Fresh pieces from the stock.
+    Stock,
+
+    /// Captured pieces transferred to the player's hand.
+    Hand,
+}
+
 #[derive(Debug)]
 pub struct PieceState {
     /// The number of pieces in stock for this model.
     stock: Count,
 
+    /// The number of captured pieces in hand for this model.
+    hand: usize,
+
     /// The number of pieces captured for this model.
     captured: usize,
+
+    /// Whether the player can use this model.
+    available: bool,
 }
 
 impl PieceState {
+    /// Returns if the player can use this model.
+    pub fn is_available(&self) -> bool {
+        self.available
+    }
+
     /// Returns the number of in stock count.
     pub fn stock(&self) -> Count {
         self.stock
     }
 
+    /// Returns the number of pieces in hand.
+    pub fn hand(&self) -> usize {
+        self.hand
+    }
+
+    /// Returns if no piece can be taken from the given source.
+    pub fn is_depleted(&self, source: PieceSource) -> bool {
+        match source {
+            PieceSource::Stock => self.stock.is_depleted(),
+            PieceSource::Hand => self.hand == 0,
+        }
+    }
+
     /// Returns the number of captured count.
     pub fn captured(&self) -> usize {
         self.captured
     }
 
-    /// Tries to take a piece from stock.
-    pub fn try_take_stock(&mut self) -> Result<(), GameError> {
-        Ok(self.stock.decrease()?)
+    /// Tries to take a piece from the given source.
+    pub fn try_take(&mut self, source: PieceSource) -> Result<(), GameError> {
+        match source {
+            PieceSource::Stock => Ok(self.stock.decrease()?),
+            PieceSource::Hand => {
+                self.hand = self.hand.checked_sub(1).ok_or(RulesError::CountDepleted)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns a captured piece to stock.
+    pub fn return_to_stock(&mut self) {
+        self.stock.increase();
+    }
+
+    /// Adds a captured piece to hand.
+    pub fn add_to_hand(&mut self) {
+        self.hand += 1;
     }
 
     /// Records a capture for this piece model.
@@ -44,6 +95,12 @@ pub struct Player {
     /// The current state of the player.
     state: PlayerState,
 
+    /// The turn number in which the player lost.
+    lost_in_turn: Option<i64>,
+
+    /// The latest score, if the player has one.
+    score: Option<i64>,
+
     /// The piece state for the player.
     ///
     /// Uses [`IndexMap`] to ensure a stable iteration order.
@@ -51,17 +108,21 @@ pub struct Player {
 }
 
 impl Player {
-    pub fn new(rules: &CheckedGameRules) -> Self {
+    pub fn new(rules: &CheckedGameRules, color: PieceColor) -> Self {
         Self {
             state: PlayerState::Active,
+            lost_in_turn: None,
+            score: None,
             piece: rules
                 .pieces()
                 .map(|(model, rules)| {
                     (
                         model,
                         PieceState {
-                            stock: rules.count(),
+                            stock: rules.count_for(color),
+                            hand: 0,
                             captured: 0,
+                            available: rules.is_available_to(color),
                         },
                     )
                 })
@@ -79,6 +140,22 @@ impl Player {
         self.state = state;
     }
 
+    /// Sets the latest score of the player.
+    pub fn set_score(&mut self, score: Option<i64>) {
+        self.score = score;
+    }
+
+    /// Marks the player as lost in the given turn.
+    pub fn lose(&mut self, turn_number: i64) {
+        self.state = PlayerState::Lost;
+        self.lost_in_turn = Some(turn_number);
+    }
+
+    /// Returns the latest score of the player.
+    pub fn score(&self) -> Option<i64> {
+        self.score
+    }
+
     /// Returns the piece state for the specified model.
     pub fn piece(&self, model: PieceModel) -> &PieceState {
         self.piece.get(&model).expect("No such piece model found")
@@ -108,7 +185,7 @@ impl Players {
         Self {
             map: rules
                 .players()
-                .map(|(color, _)| (color, Player::new(rules)))
+                .map(|(color, _)| (color, Player::new(rules, color)))
                 .collect(),
         }
     }
@@ -154,12 +231,214 @@ impl Players {
         self.map.iter_mut().map(|(color, player)| (*color, player))
     }
 
+    /// Records the capture of a piece, sending it where the capture policy says.
+    ///
+    /// A transferred piece the capturer cannot use leaves the game instead.
+    pub fn capture(
+        &mut self,
+        policy: &CapturePolicy,
+        captured: &PlacedPiece,
+        capturer: PieceColor,
+    ) {
+        let owner = captured.color();
+        let model = captured.model();
+
+        self.get_by_color_mut(owner)
+            .piece_mut(model)
+            .record_capture();
+
+        match policy {
+            CapturePolicy::Discard => {}
+            // Promoted pieces return as the model they were placed as, which their stock counts.
+            CapturePolicy::ReturnToOwner => self
+                .get_by_color_mut(owner)
+                .piece_mut(policy.convert(captured.placed_as()))
+                .return_to_stock(),
+            CapturePolicy::Transfer(_) => {
+                let piece = self
+                    .get_by_color_mut(capturer)
+                    .piece_mut(policy.convert(model));
+
+                if piece.is_available() {
+                    piece.add_to_hand();
+                }
+            }
+        }
+    }
+
+    /// Returns if any player has a score.
+    pub fn has_scores(&self) -> bool {
+        self.map.values().any(|player| player.score.is_some())
+    }
+
+    /// Returns the final standings as (rank, color, state), ranked from 1.
+    ///
+    /// Winners come first, then drawn and remaining active players, then losers
+    /// with the last to lose ranked highest. Within each result, higher scores rank
+    /// higher, remaining ties are resolved by the tie-break policy.
+    pub fn standings(&self, tie_break: TieBreak) -> Vec<(usize, PieceColor, PlayerState)> {
+        let result = |index: usize, player: &Player| {
+            let (group, lost) = match player.state() {
+                PlayerState::Won => (0, 0),
+                PlayerState::Draw | PlayerState::Active => (1, 0),
+                PlayerState::Lost => (2, -player.lost_in_turn.unwrap_or(0)),
+            };
+
+            let order = match tie_break {
+                TieBreak::Shared => 0,
+                TieBreak::PlayerOrder => index as i64,
+                TieBreak::ReversePlayerOrder => -(index as i64),
+            };
+
+            (group, lost, -player.score.unwrap_or(0), order)
+        };
+
+        let mut ranked: Vec<(usize, PieceColor, &Player)> = self
+            .iter()
+            .enumerate()
+            .map(|(index, (color, player))| (index, color, player))
+            .collect();
+        ranked.sort_by_key(|(index, _, player)| result(*index, player));
+
+        let mut standings = Vec::new();
+        let mut prev = None;
+
+        for (position, (index, color, player)) in ranked.into_iter().enumerate() {
+            let key = result(index, player);
+
+            // Ties share the rank of the first player with the same result.
+            let rank = match prev {
+                Some((prev_key, prev_rank)) if prev_key == key => prev_rank,
+                _ => position + 1,
+            };
+
+            prev = Some((key, rank));
+            standings.push((rank, color, player.state()));
+        }
+
+        standings
+    }
+
     /// Returns formatted string for the player states.
-    pub fn player_states_message(&self) -> String {
+    pub fn player_states_message(&self, rules: &CheckedGameRules, strings: &Strings) -> String {
         self.map
             .iter()
-            .map(|(color, player)| format!("{}[{}]", color, player.state()))
+            .map(|(color, player)| {
+                format!(
+                    "{}[{}]",
+                    rules.color_name(*color),
+                    strings.state(player.state())
+                )
+            })
             .collect::<Vec<String>>()
-            .join(", ")
+            .join(strings.separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::playing::session::tests::{placed, rules_with};
+
+    /// Rules with a `Crown` only Black can use.
+    fn capture_rules(capture_policy: &str) -> CheckedGameRules {
+        rules_with(
+            "Stone: (count: Finite(3), movement: True, placement: True),
+            Crown: (count: Finite(1), movement: True, placement: True, players: Some([Black]))",
+            &["White", "Black"],
+            &format!("capture_policy: {capture_policy},"),
+        )
+    }
+
+    #[test]
+    fn captured_pieces_follow_the_policy() {
+        let white = PieceColor::new("White");
+        let black = PieceColor::new("Black");
+        let stone = PieceModel::new("Stone");
+
+        let rules = capture_rules("ReturnToOwner");
+        let mut players = Players::new(&rules);
+
+        players.capture(
+            rules.capture_policy(),
+            &placed("Stone", "Black", 0, 0),
+            white,
+        );
+
+        let piece = players.get_by_color(black).piece(stone);
+        assert!(matches!(piece.stock(), Count::Finite(4)));
+        assert_eq!(piece.captured(), 1);
+
+        let rules = capture_rules("Transfer({})");
+        let mut players = Players::new(&rules);
+
+        players.capture(
+            rules.capture_policy(),
+            &placed("Stone", "Black", 0, 0),
+            white,
+        );
+
+        assert_eq!(players.get_by_color(white).piece(stone).hand(), 1);
+        assert!(matches!(
+            players.get_by_color(black).piece(stone).stock(),
+            Count::Finite(3)
+        ));
+    }
+
+    #[test]
+    fn promoted_pieces_return_as_placed() {
+        let white = PieceColor::new("White");
+        let black = PieceColor::new("Black");
+
+        let rules = capture_rules("ReturnToOwner");
+        let mut players = Players::new(&rules);
+
+        let mut promoted = placed("Stone", "Black", 0, 0);
+        promoted.set_model(PieceModel::new("Crown"));
+
+        players.capture(rules.capture_policy(), &promoted, white);
+
+        // The stone comes back, the crown's stock stays within its count.
+        let player = players.get_by_color(black);
+        assert!(matches!(
+            player.piece(PieceModel::new("Stone")).stock(),
+            Count::Finite(4)
+        ));
+        assert!(matches!(
+            player.piece(PieceModel::new("Crown")).stock(),
+            Count::Finite(1)
+        ));
+        assert_eq!(player.piece(PieceModel::new("Crown")).captured(), 1);
+    }
+
+    #[test]
+    fn unusable_transfers_leave_the_game() {
+        let white = PieceColor::new("White");
+        let black = PieceColor::new("Black");
+        let crown = PieceModel::new("Crown");
+
+        let rules = capture_rules("Transfer({})");
+        let mut players = Players::new(&rules);
+
+        players.capture(
+            rules.capture_policy(),
+            &placed("Crown", "Black", 0, 0),
+            white,
+        );
+
+        assert_eq!(players.get_by_color(white).piece(crown).hand(), 0);
+        assert_eq!(players.get_by_color(black).piece(crown).captured(), 1);
+
+        // Converted models are checked instead of the captured one.
+        let rules = capture_rules("Transfer({Stone: Crown})");
+        let mut players = Players::new(&rules);
+
+        players.capture(
+            rules.capture_policy(),
+            &placed("Stone", "Black", 0, 0),
+            white,
+        );
+
+        assert_eq!(players.get_by_color(white).piece(crown).hand(), 0);
     }
 }
@@ -0,0 +1,90 @@
+use crate::GameError;
+use std::{
+    cell::Cell,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Increment of the SplitMix64 generator.
+const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A seeded random number generator (SplitMix64).
+///
+/// Only advanced by the turn flow, so a seed and the same actions reproduce a game.
+#[derive(Debug)]
+pub struct SessionRng {
+    /// The seed the generator started from.
+    seed: u64,
+
+    /// The current state of the generator.
+    state: u64,
+}
+
+impl SessionRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Creates a generator seeded from the system time.
+    pub fn from_time() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self::new(seed)
+    }
+
+    /// Returns the seed the generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GAMMA);
+        mix(self.state)
+    }
+}
+
+/// Numbers the random expressions evaluated by one context, so each gets its own value.
+///
+/// A fresh context counts from zero again, so evaluating the same rules gives the same values.
+#[derive(Debug, Default)]
+pub struct RandomCalls(Cell<u64>);
+
+impl RandomCalls {
+    /// Returns the index of the next random expression.
+    pub fn next(&self) -> u64 {
+        let call = self.0.get();
+        self.0.set(call + 1);
+        call
+    }
+}
+
+/// Picks an integer between min and max, inclusive, from a stored draw.
+///
+/// The same draw, call index and range always give the same integer, however often it is picked.
+pub fn pick(draw: u64, call: u64, min: i64, max: i64) -> Result<i64, GameError> {
+    if min > max {
+        return Err(GameError::InvalidRandomRange(min, max));
+    }
+
+    // Different calls and ranges pick independent values from the same draw.
+    let value = mix(draw ^ mix(call ^ mix(min as u64 ^ mix(max as u64))));
+    let span = max.abs_diff(min).wrapping_add(1);
+
+    // A span of zero means the full range of `u64`.
+    let offset = match span {
+        0 => value,
+        span => value % span,
+    };
+
+    Ok(min.wrapping_add_unsigned(offset))
+}
+
+/// The output function of SplitMix64.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,78 @@
+use crate::{locale::Strings, states::playing::session::player::Players};
+use indexmap::IndexMap;
+use rulery::{CheckedGameRules, piece::PieceColor, player::PlayerState};
+
+/// Teams of the game.
+///
+/// Players that are not declared in any team form a team of their own,
+/// named after their color.
+#[derive(Debug)]
+pub struct Teams {
+    /// Uses [`IndexMap`] to ensure a stable iteration order.
+    map: IndexMap<String, Vec<PieceColor>>,
+}
+
+impl Teams {
+    pub fn new(rules: &CheckedGameRules) -> Self {
+        let mut map: IndexMap<String, Vec<PieceColor>> = rules
+            .teams()
+            .map(|(name, members)| (name.to_string(), members.to_vec()))
+            .collect();
+
+        for (color, _) in rules.players() {
+            if !map.values().any(|members| members.contains(&color)) {
+                map.entry(color.to_string()).or_default().push(color);
+            }
+        }
+
+        Self { map }
+    }
+
+    /// Returns if two colors play on the same team.
+    pub fn same_team(&self, a: PieceColor, b: PieceColor) -> bool {
+        self.map
+            .values()
+            .any(|members| members.contains(&a) && members.contains(&b))
+    }
+
+    /// Returns the members of the team with the given color.
+    pub fn allies(&self, color: PieceColor) -> &[PieceColor] {
+        self.map
+            .values()
+            .find(|members| members.contains(&color))
+            .expect("No such team member found")
+    }
+
+    /// Returns the state of the team with the given name.
+    ///
+    /// A team has won if any member has won, and lost once all members have lost.
+    pub fn state(&self, name: &str, players: &Players) -> Option<PlayerState> {
+        let members = self.map.get(name)?;
+        let mut states = members
+            .iter()
+            .map(|color| players.get_by_color(*color).state());
+
+        if states.clone().any(|state| state == PlayerState::Won) {
+            Some(PlayerState::Won)
+        } else if states.all(|state| state == PlayerState::Lost) {
+            Some(PlayerState::Lost)
+        } else {
+            Some(PlayerState::Active)
+        }
+    }
+
+    /// Returns formatted string for the team states.
+    pub fn team_states_message(&self, players: &Players, strings: &Strings) -> String {
+        self.map
+            .keys()
+            .map(|name| {
+                format!(
+                    "{}[{}]",
+                    name,
+                    strings.state(self.state(name, players).unwrap())
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(strings.separator)
+    }
+}
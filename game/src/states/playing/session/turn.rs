@@ -1,5 +1,27 @@
-use crate::{GameError, states::playing::session::player::Players};
-use rulery::player::PlayerState;
+use crate::{
+    GameError,
+    locale::{Strings, fill},
+    states::playing::session::{
+        player::Players,
+        random::{SessionRng, pick},
+    },
+};
+use rulery::{
+    CheckedGameRules,
+    piece::PieceColor,
+    player::PlayerState,
+    turn::{Dice, TurnOrder, TurnSchedule},
+};
+
+/// The player taking the next turn.
+#[derive(Debug, Clone, Copy)]
+pub struct NextTurn {
+    /// The index of the next player.
+    player: usize,
+
+    /// Whether a new round begins with the next turn.
+    new_round: bool,
+}
 
 /// Controls the turn-based flow of the game.
 #[derive(Debug)]
@@ -12,15 +34,89 @@ pub struct TurnController {
 
     /// The current round number, starting from 1.
     round_number: i64,
+
+    /// The number of actions in the current turn.
+    actions_in_turn: usize,
+
+    /// The number of actions taken in the current turn.
+    actions_taken: usize,
+
+    /// The seeded generator behind dice and random draws.
+    rng: SessionRng,
+
+    /// The dice value rolled for every turn so far, indexed by turn number.
+    dice_rolls: Vec<i64>,
+
+    /// The random draw of every action so far, read by random expressions.
+    draws: Vec<u64>,
 }
 
 impl TurnController {
-    pub fn new() -> Self {
-        Self {
-            current_player: 0,
+    pub fn new(schedule: &TurnSchedule, num_players: usize, mut rng: SessionRng) -> Self {
+        // Pick the first player before the first turn is recorded.
+        let current_player = if schedule.random_first_player() && num_players > 0 {
+            (rng.next_u64() % num_players as u64) as usize
+        } else {
+            0
+        };
+
+        let mut turn = Self {
+            current_player,
             turn_number: 1,
             round_number: 1,
-        }
+            actions_in_turn: schedule.actions_in_turn(1),
+            actions_taken: 0,
+            rng,
+            dice_rolls: Vec::new(),
+            draws: Vec::new(),
+        };
+
+        turn.roll(schedule.dice());
+        turn.draw();
+        turn
+    }
+
+    /// Rolls the dice for the current turn and records the value.
+    fn roll(&mut self, dice: Option<&Dice>) {
+        let Some(dice) = dice else {
+            return;
+        };
+
+        let value = (0..dice.count())
+            .map(|_| (self.rng.next_u64() % u64::from(dice.faces())) as i64 + 1)
+            .sum();
+
+        self.dice_rolls.push(value);
+    }
+
+    /// Draws the randomness of the current action and records it.
+    fn draw(&mut self) {
+        let draw = self.rng.next_u64();
+        self.draws.push(draw);
+    }
+
+    /// Returns the dice value rolled for the current turn.
+    pub fn dice_value(&self) -> Option<i64> {
+        self.dice_rolls.last().copied()
+    }
+
+    /// Returns a random integer between min and max, inclusive, for the `call`-th random
+    /// expression of an evaluation.
+    ///
+    /// Reads the draw of the current action, so evaluating it again gives the same integer.
+    pub fn random(&self, call: u64, min: i64, max: i64) -> Result<i64, GameError> {
+        let draw = self.draws.last().copied().unwrap_or_default();
+        pick(draw, call, min, max)
+    }
+
+    /// Returns the seed of the game.
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    /// Returns the dice value rolled for every turn so far.
+    pub fn dice_rolls(&self) -> &[i64] {
+        &self.dice_rolls
     }
 
     /// Returns the current player index.
@@ -28,32 +124,124 @@ impl TurnController {
         self.current_player
     }
 
-    /// Advances the turn to the next player.
-    pub fn advance_turn(&mut self, players: &Players) -> Result<(), GameError> {
-        // Search for the next active player, checking each player at most once.
-        for offset in 1..=players.num() {
-            let next_index = (self.current_player + offset) % players.num();
-
-            // Only pick active player.
-            if players.get_by_index(next_index).1.state() == PlayerState::Active {
-                // A new round begins if the turn has wrapped around to a player at or before
-                // the previous player's index.
-                if next_index <= self.current_player {
-                    self.round_number += 1;
+    /// Records an action, returns if the current turn has actions left.
+    pub fn record_action(&mut self) -> bool {
+        self.actions_taken += 1;
+
+        let has_actions_left = self.actions_taken < self.actions_in_turn;
+
+        // The next action of the turn draws anew.
+        if has_actions_left {
+            self.draw();
+        }
+
+        has_actions_left
+    }
+
+    /// Returns the number of actions left in the current turn.
+    pub fn actions_left(&self) -> usize {
+        self.actions_in_turn.saturating_sub(self.actions_taken)
+    }
+
+    /// Returns the index of the player to act after the current action and the actions they have
+    /// left, `next` being the next turn if the current one ends with the action.
+    pub fn to_move(&self, schedule: &TurnSchedule, next: Option<NextTurn>) -> (usize, usize) {
+        match next {
+            Some(next) => (next.player, schedule.actions_in_turn(self.turn_number + 1)),
+            None => (self.current_player, self.actions_left().saturating_sub(1)),
+        }
+    }
+
+    /// Finds the player taking the next turn.
+    ///
+    /// `is_next` is only consulted for [`TurnOrder::Custom`].
+    pub fn next_player<F>(
+        &self,
+        schedule: &TurnSchedule,
+        players: &Players,
+        mut is_next: F,
+    ) -> Result<NextTurn, GameError>
+    where
+        F: FnMut(PieceColor) -> Result<bool, GameError>,
+    {
+        let is_active = |index: usize| players.get_by_index(index).1.state() == PlayerState::Active;
+
+        match schedule.order() {
+            TurnOrder::RoundRobin | TurnOrder::Snake => {
+                let order = |round: i64| -> Vec<usize> {
+                    // Snake order reverses every other round.
+                    if matches!(schedule.order(), TurnOrder::Snake) && round % 2 == 0 {
+                        (0..players.num()).rev().collect()
+                    } else {
+                        (0..players.num()).collect()
+                    }
+                };
+
+                // Search the rest of the current round first.
+                let current = order(self.round_number);
+                let rest = current
+                    .iter()
+                    .skip_while(|index| **index != self.current_player)
+                    .skip(1);
+
+                for index in rest {
+                    if is_active(*index) {
+                        return Ok(NextTurn {
+                            player: *index,
+                            new_round: false,
+                        });
+                    }
                 }
 
-                // Update the current player and increment the turn number.
-                self.current_player = next_index;
-                self.turn_number += 1;
+                // Then the next round.
+                for index in order(self.round_number + 1) {
+                    if is_active(index) {
+                        return Ok(NextTurn {
+                            player: index,
+                            new_round: true,
+                        });
+                    }
+                }
+            }
+            TurnOrder::Custom(_) => {
+                // Search for the next picked player, checking each player at most once.
+                for offset in 1..=players.num() {
+                    let index = (self.current_player + offset) % players.num();
 
-                return Ok(());
+                    if is_active(index) && is_next(players.get_by_index(index).0)? {
+                        return Ok(NextTurn {
+                            player: index,
+                            // A new round begins if the turn has wrapped around to a player at or before
+                            // the previous player's index.
+                            new_round: index <= self.current_player,
+                        });
+                    }
+                }
             }
         }
 
-        // If the loop completes, no active players were found.
+        // If the search completes, no active players were found.
         Err(GameError::NoActivePlayer)
     }
 
+    /// Advances the turn to the next player.
+    pub fn advance_turn(&mut self, schedule: &TurnSchedule, next: NextTurn) {
+        if next.new_round {
+            self.round_number += 1;
+        }
+
+        // Update the current player and increment the turn number.
+        self.current_player = next.player;
+        self.turn_number += 1;
+
+        // Reset the actions for the new turn.
+        self.actions_in_turn = schedule.actions_in_turn(self.turn_number);
+        self.actions_taken = 0;
+
+        self.roll(schedule.dice());
+        self.draw();
+    }
+
     /// Returns current turn number.
     pub fn turn_number(&self) -> i64 {
         self.turn_number
@@ -65,12 +253,120 @@ impl TurnController {
     }
 
     /// Returns formatted string for the current turn message.
-    pub fn turn_message(&self, players: &Players) -> String {
-        format!(
-            "{}'s Turn — Turn {}, Round {}",
-            players.get_by_index(self.current_player).0,
-            self.turn_number,
-            self.round_number
-        )
+    pub fn turn_message(
+        &self,
+        rules: &CheckedGameRules,
+        players: &Players,
+        strings: &Strings,
+    ) -> String {
+        let mut parts = vec![fill(
+            strings.turn,
+            &[
+                (
+                    "player",
+                    &rules.color_name(players.get_by_index(self.current_player).0),
+                ),
+                ("turn", &self.turn_number),
+                ("round", &self.round_number),
+            ],
+        )];
+
+        if self.actions_in_turn > 1 {
+            parts.push(fill(
+                strings.actions_left,
+                &[
+                    ("left", &self.actions_left()),
+                    ("total", &self.actions_in_turn),
+                ],
+            ));
+        }
+
+        if let Some(dice_value) = self.dice_value() {
+            parts.push(fill(strings.rolled, &[("value", &dice_value)]));
+        }
+
+        parts.join(strings.separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::playing::session::random::RandomCalls;
+
+    /// Plays a few turns of two actions, evaluating random expressions `evaluations` times per action.
+    fn play(seed: u64, evaluations: usize) -> (Vec<i64>, Vec<i64>, Vec<u64>) {
+        let mut schedule = TurnSchedule::new(TurnOrder::RoundRobin, vec![2]);
+        schedule.set_dice(Some(Dice::new(2, 6)));
+
+        let mut turn = TurnController::new(&schedule, 2, SessionRng::new(seed));
+        let mut values = Vec::new();
+
+        for _ in 0..4 {
+            let action: Vec<i64> = (0..evaluations)
+                .map(|_| turn.random(0, 1, 100).unwrap())
+                .collect();
+
+            // Repeated evaluations within an action agree.
+            assert!(action.windows(2).all(|pair| pair[0] == pair[1]));
+            values.push(action[0]);
+
+            if !turn.record_action() {
+                let next = NextTurn {
+                    player: (turn.current_player() + 1) % 2,
+                    new_round: turn.current_player() == 1,
+                };
+
+                turn.advance_turn(&schedule, next);
+            }
+        }
+
+        (values, turn.dice_rolls().to_vec(), turn.draws.clone())
+    }
+
+    #[test]
+    fn same_seed_and_actions_give_same_draws() {
+        let (values, dice_rolls, draws) = play(42, 1);
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(dice_rolls.len(), 3);
+        assert_eq!(draws.len(), 5);
+
+        // Evaluating more often, e.g. to list legal moves, draws nothing more.
+        assert_eq!(play(42, 5), (values, dice_rolls, draws));
+        assert_ne!(play(43, 1).2, play(42, 1).2);
+    }
+
+    #[test]
+    fn random_stays_in_range() {
+        let turn = TurnController::new(&TurnSchedule::default(), 2, SessionRng::new(7));
+
+        assert_eq!(turn.random(0, 3, 3).unwrap(), 3);
+        assert!((-2..=2).contains(&turn.random(0, -2, 2).unwrap()));
+        assert!(turn.random(0, 2, 1).is_err());
+    }
+
+    #[test]
+    fn random_calls_in_one_action_differ() {
+        // Row and column of a random tile, picked by one evaluation.
+        let tile = |turn: &TurnController| {
+            let calls = RandomCalls::default();
+
+            (
+                turn.random(calls.next(), 0, 8).unwrap(),
+                turn.random(calls.next(), 0, 8).unwrap(),
+            )
+        };
+
+        let turns: Vec<TurnController> = (0..16)
+            .map(|seed| TurnController::new(&TurnSchedule::default(), 2, SessionRng::new(seed)))
+            .collect();
+
+        // Evaluating again repeats the tile, but it is not stuck on the diagonal.
+        assert!(turns.iter().all(|turn| tile(turn) == tile(turn)));
+        assert!(turns.iter().any(|turn| {
+            let (row, col) = tile(turn);
+            row != col
+        }));
     }
 }
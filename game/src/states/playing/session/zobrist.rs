@@ -0,0 +1,99 @@
+use rulery::{
+    count::Count,
+    piece::{PieceColor, PieceModel},
+    pos::Pos,
+};
+use std::collections::HashMap;
+
+/// Tags that keep the keys of different features apart.
+const PIECE: u64 = 1;
+const STOCK: u64 = 2;
+const TO_MOVE: u64 = 3;
+
+/// Returns the Zobrist key of a piece at a level of the stack on a tile.
+pub fn piece_key(model: PieceModel, color: PieceColor, pos: Pos, level: usize) -> u64 {
+    key(&[
+        PIECE,
+        name_hash(model.name()),
+        name_hash(color.name()),
+        pos.row() as u64,
+        pos.col() as u64,
+        level as u64,
+    ])
+}
+
+/// Returns the Zobrist key of a player's stock and hand of a piece kind.
+pub fn stock_key(color: PieceColor, model: PieceModel, stock: Count, hand: usize) -> u64 {
+    let stock = match stock {
+        Count::Infinite => u64::MAX,
+        Count::Finite(n) => n as u64,
+    };
+
+    key(&[
+        STOCK,
+        name_hash(color.name()),
+        name_hash(model.name()),
+        stock,
+        hand as u64,
+    ])
+}
+
+/// Returns the Zobrist key of the player to act next and the actions they have left.
+pub fn to_move_key(color: PieceColor, actions_left: usize) -> u64 {
+    key(&[TO_MOVE, name_hash(color.name()), actions_left as u64])
+}
+
+/// Derives a key from the features instead of a random table,
+/// so every model, color and position has one without knowing the rules in advance.
+fn key(features: &[u64]) -> u64 {
+    features
+        .iter()
+        .fold(0x2545_F491_4F6C_DD1D, |acc, feature| mix(acc ^ feature))
+}
+
+/// The SplitMix64 finalizer.
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// FNV-1a hash of a name.
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xCBF2_9CE4_8422_2325, |acc, byte| {
+        (acc ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+    })
+}
+
+/// Hashes of the positions reached so far.
+#[derive(Debug, Default)]
+pub struct PositionHistory {
+    /// How many times each position has occurred.
+    counts: HashMap<u64, i64>,
+
+    /// The hash of the latest position.
+    current: u64,
+}
+
+impl PositionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a reached position.
+    pub fn record(&mut self, hash: u64) {
+        *self.counts.entry(hash).or_default() += 1;
+        self.current = hash;
+    }
+
+    /// Returns the hash of the latest position.
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Returns how many times the latest position has occurred, including now.
+    pub fn repetition_count(&self) -> i64 {
+        self.counts.get(&self.current).copied().unwrap_or_default()
+    }
+}
@@ -1,8 +1,14 @@
-use crate::states::{
-    AppState,
-    playing::{
-        phases::{GamePhase, placing::start_place_piece},
-        session::GameSession,
+use crate::{
+    locale::fill,
+    settings::Settings,
+    states::{
+        AppState,
+        game_setup::LoadedRules,
+        playing::{
+            markdown::markdown,
+            phases::{GamePhase, placing::start_place_piece},
+            session::{GameSession, player::PieceSource},
+        },
     },
 };
 use bevy::prelude::*;
@@ -10,11 +16,18 @@ use bevy_egui::{
     EguiContexts,
     egui::{self, Stroke},
 };
+use rulery::piece::Rgb;
 
 #[derive(Resource)]
 pub struct TopPanelText(pub String);
 
-pub fn top_panel(mut egui: EguiContexts, text: Res<TopPanelText>) {
+pub fn top_panel(
+    mut egui: EguiContexts,
+    text: Res<TopPanelText>,
+    session: Res<GameSession>,
+    rules: Res<LoadedRules>,
+    settings: Res<Settings>,
+) {
     egui::TopBottomPanel::top("top_panel")
         .frame(
             egui::Frame::NONE
@@ -23,10 +36,20 @@ pub fn top_panel(mut egui: EguiContexts, text: Res<TopPanelText>) {
                 .inner_margin(egui::Margin::symmetric(0, 10)),
         )
         .show(egui.ctx_mut().unwrap(), |ui_ctx| {
-            ui_ctx.with_layout(
-                egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
-                |ui_ctx| ui_ctx.label(egui::RichText::new(&text.0).strong().size(24.0)),
-            );
+            ui_ctx.vertical_centered(|ui_ctx| {
+                ui_ctx.label(egui::RichText::new(&text.0).strong().size(24.0));
+
+                // Clocks of timed games
+                if session.clocks.is_enabled() {
+                    ui_ctx.label(
+                        egui::RichText::new(
+                            session.clocks.message(&rules, settings.locale.strings()),
+                        )
+                        .size(18.0)
+                        .monospace(),
+                    );
+                }
+            });
         });
 }
 
@@ -34,6 +57,8 @@ pub fn bottom_panel(
     mut commands: Commands,
     mut egui: EguiContexts,
     mut session: ResMut<GameSession>,
+    rules: Res<LoadedRules>,
+    settings: Res<Settings>,
     next_state: Res<NextState<AppState>>,
     current_phase: Res<State<GamePhase>>,
     mut next_phase: ResMut<NextState<GamePhase>>,
@@ -43,6 +68,7 @@ pub fn bottom_panel(
     }
 
     let session = session.as_mut();
+    let strings = settings.locale.strings();
 
     egui::TopBottomPanel::bottom("bottom_panel")
         .frame(
@@ -56,21 +82,42 @@ pub fn bottom_panel(
             egui::ScrollArea::horizontal().show(ui, |ui| {
                 // Row 1: In Stock
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("In Stock").size(18.0).monospace())
-                        .on_hover_text("Number of your pieces available for placement");
+                    ui.label(egui::RichText::new(strings.in_stock).size(18.0).monospace())
+                        .on_hover_text(strings.in_stock_hint);
 
                     ui.separator();
 
-                    for (model, piece) in player.pieces() {
-                        let button = egui::Button::new(
-                            egui::RichText::new(format!("{} × {}", model, piece.stock()))
-                                .size(18.0)
-                                .strong(),
-                        );
+                    // Captured pieces in hand are listed after the fresh stock
+                    // Models the player can't use are left out
+                    let stock = player
+                        .pieces()
+                        .filter(|(_, piece)| piece.is_available())
+                        .map(|(model, piece)| (model, piece, PieceSource::Stock));
+
+                    let hand = player
+                        .pieces()
+                        .filter(|(_, piece)| piece.hand() > 0)
+                        .map(|(model, piece)| (model, piece, PieceSource::Hand));
+
+                    for (model, piece, source) in stock.chain(hand) {
+                        let name = rules.model_name(model);
+
+                        let text = match source {
+                            PieceSource::Stock => format!("{} × {}", name, piece.stock()),
+                            PieceSource::Hand => fill(
+                                strings.in_hand,
+                                &[("name", &name), ("count", &piece.hand())],
+                            ),
+                        };
+
+                        let button = egui::Button::new(label_text(
+                            egui::RichText::new(text).size(18.0).strong(),
+                            rules.model_rgb(model),
+                        ));
 
                         // Enable the button if the session is in selecting state and the count is not depleted
                         let enabled = matches!(current_phase.get(), GamePhase::Selecting)
-                            && !piece.stock().is_depleted();
+                            && !piece.is_depleted(source);
 
                         if ui.add_enabled(enabled, button).clicked() {
                             // Avoid duplicate transition
@@ -81,6 +128,7 @@ pub fn bottom_panel(
                                     &mut next_phase,
                                     model,
                                     piece_color,
+                                    source,
                                 );
                             }
                         }
@@ -91,20 +139,89 @@ pub fn bottom_panel(
 
                 // Row 2: Captured
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Captured").size(18.0).monospace())
-                        .on_hover_text("Number of your pieces that have been captured");
+                    ui.label(egui::RichText::new(strings.captured).size(18.0).monospace())
+                        .on_hover_text(strings.captured_hint);
 
                     ui.separator();
 
-                    for (model, piece) in player.pieces() {
-                        let button = egui::Button::new(
-                            egui::RichText::new(format!("{} × {}", model, piece.captured()))
-                                .size(18.0),
-                        );
+                    for (model, piece) in player
+                        .pieces()
+                        .filter(|(_, piece)| piece.is_available() || piece.captured() > 0)
+                    {
+                        let button = egui::Button::new(label_text(
+                            egui::RichText::new(format!(
+                                "{} × {}",
+                                rules.model_name(model),
+                                piece.captured()
+                            ))
+                            .size(18.0),
+                            rules.model_rgb(model),
+                        ));
 
                         ui.add_enabled(false, button);
                     }
                 });
+
+                // Row 3: Score
+                if session.players.has_scores() {
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(strings.score).size(18.0).monospace())
+                            .on_hover_text(strings.score_hint);
+
+                        ui.separator();
+
+                        for (color, player) in session.players.iter() {
+                            let Some(score) = player.score() else {
+                                continue;
+                            };
+
+                            let button = egui::Button::new(label_text(
+                                egui::RichText::new(format!(
+                                    "{}: {score}",
+                                    rules.color_name(color)
+                                ))
+                                .size(18.0),
+                                rules.color_rgb(color),
+                            ));
+
+                            ui.add_enabled(false, button);
+                        }
+                    });
+                }
             });
         });
 }
+
+/// Shows the description and how to play in a collapsible window.
+pub fn rules_panel(mut egui: EguiContexts, rules: Res<LoadedRules>, settings: Res<Settings>) {
+    if rules.description().is_none() && rules.how_to_play().is_none() {
+        return;
+    }
+
+    egui::Window::new(settings.locale.strings().rules)
+        .default_open(false)
+        .default_width(420.0)
+        .max_height(480.0)
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 90.0])
+        .vscroll(true)
+        .show(egui.ctx_mut().unwrap(), |ui| {
+            if let Some(description) = rules.description() {
+                ui.label(egui::RichText::new(description).size(16.0).italics());
+                ui.separator();
+            }
+
+            if let Some(how_to_play) = rules.how_to_play() {
+                markdown(ui, how_to_play);
+            }
+        });
+}
+
+/// Applies a declared label color, if any.
+fn label_text(text: egui::RichText, rgb: Option<Rgb>) -> egui::RichText {
+    match rgb {
+        Some(Rgb(r, g, b)) => text.color(egui::Color32::from_rgb(r, g, b)),
+        None => text,
+    }
+}
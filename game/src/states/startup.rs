@@ -1,4 +1,9 @@
-use crate::{assets::GameAssets, settings::Settings, states::AppState};
+use crate::{
+    assets::GameAssets,
+    locale::UiFont,
+    settings::Settings,
+    states::{AppState, error::CurrentError},
+};
 use bevy::{
     app::{App, Plugin, Startup},
     asset::Assets,
@@ -6,6 +11,7 @@ use bevy::{
     mesh::Mesh,
     pbr::StandardMaterial,
     state::state::NextState,
+    text::Font,
 };
 
 pub struct StartupPlugin;
@@ -20,14 +26,36 @@ fn on_startup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut fonts: ResMut<Assets<Font>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
     // Load setting
-    commands.insert_resource(Settings::load("game/settings.ron").unwrap());
+    let settings = Settings::load("game/settings.ron").unwrap();
+
+    // Load the font for the chosen locale, if any
+    let font = settings
+        .font
+        .as_deref()
+        .map(|path| UiFont::load(path, &mut fonts))
+        .transpose();
+
+    commands.insert_resource(settings);
 
     // Load assets
     commands.insert_resource(GameAssets::new(&mut materials, &mut meshes));
 
-    // Switch to the `Menu` state once the startup is complete
-    next_state.set(AppState::Menu);
+    match font {
+        Ok(font) => {
+            if let Some(font) = font {
+                commands.insert_resource(font);
+            }
+
+            // Switch to the `Menu` state once the startup is complete
+            next_state.set(AppState::Menu);
+        }
+        Err(err) => {
+            commands.insert_resource(CurrentError(err));
+            next_state.set(AppState::Error);
+        }
+    }
 }
@@ -1,14 +1,106 @@
+use crate::pos::Pos;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Shape of the board tiles and how positions are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Topology {
+    /// Square tiles in rows and columns.
+    #[default]
+    Square,
+
+    /// Hexagonal tiles in axial coordinates forming a rhombus (Hex).
+    ///
+    /// The row is the `r` axis and the column is the `q` axis.
+    Hex,
+
+    /// Hexagonal tiles in axial coordinates forming a hexagon (Abalone).
+    ///
+    /// Rows and columns must be equal and odd, the tiles outside the hexagon
+    /// inscribed in the rhombus are left out.
+    Hexagon,
+}
+
+impl Topology {
+    /// Returns if the tiles are hexagonal.
+    pub fn is_hex(&self) -> bool {
+        matches!(self, Topology::Hex | Topology::Hexagon)
+    }
+
+    /// Returns the (row, col) offsets of the tiles sharing an edge with a tile.
+    pub fn neighbour_offsets(&self) -> &'static [(i64, i64)] {
+        if self.is_hex() {
+            &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, -1), (-1, 1)]
+        } else {
+            &[(1, 0), (-1, 0), (0, 1), (0, -1)]
+        }
+    }
+}
+
+/// Which board axes wrap around, connecting opposite edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Wrap {
+    /// No axis wraps.
+    #[default]
+    None,
+
+    /// The last row is next to the first one.
+    Rows,
+
+    /// The last column is next to the first one (cylinder chess).
+    Cols,
+
+    /// Both axes wrap (torus).
+    Both,
+}
+
+/// Lengths of the wrapped board axes, used to normalise positions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoardWrap {
+    rows: Option<i64>,
+    cols: Option<i64>,
+}
+
+impl BoardWrap {
+    /// Returns the number of rows if rows wrap.
+    pub fn rows(&self) -> Option<i64> {
+        self.rows
+    }
+
+    /// Returns the number of columns if columns wrap.
+    pub fn cols(&self) -> Option<i64> {
+        self.cols
+    }
+
+    /// Maps a position onto the board along the wrapped axes.
+    pub fn normalize(&self, pos: Pos) -> Pos {
+        Pos::new(
+            self.rows
+                .map_or(pos.row(), |rows| pos.row().rem_euclid(rows)),
+            self.cols
+                .map_or(pos.col(), |cols| pos.col().rem_euclid(cols)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct BoardRuleSet {
     rows: i64,
     cols: i64,
+    #[serde(default)]
+    topology: Topology,
+    #[serde(default)]
+    wrap: Wrap,
 }
 
 impl BoardRuleSet {
     pub(crate) fn new() -> Self {
-        Self { rows: 8, cols: 8 }
+        Self {
+            rows: 8,
+            cols: 8,
+            topology: Topology::Square,
+            wrap: Wrap::None,
+        }
     }
 
     /// Sets the number of rows.
@@ -21,6 +113,16 @@ impl BoardRuleSet {
         self.cols = num;
     }
 
+    /// Sets the topology.
+    pub(crate) const fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Sets which axes wrap around.
+    pub(crate) const fn set_wrap(&mut self, wrap: Wrap) {
+        self.wrap = wrap;
+    }
+
     /// Returns the number of rows in the board.
     pub(crate) const fn rows(&self) -> i64 {
         self.rows
@@ -31,6 +133,55 @@ impl BoardRuleSet {
         self.cols
     }
 
+    /// Returns the topology.
+    pub(crate) const fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Returns which axes wrap around.
+    pub(crate) const fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// Returns the lengths of the wrapped axes.
+    pub(crate) fn wrap_lengths(&self) -> BoardWrap {
+        let rows = matches!(self.wrap, Wrap::Rows | Wrap::Both).then_some(self.rows);
+        let cols = matches!(self.wrap, Wrap::Cols | Wrap::Both).then_some(self.cols);
+
+        BoardWrap { rows, cols }
+    }
+
+    /// Returns if the wrap fits the topology, a hexagon cannot wrap.
+    pub(crate) fn is_wrap_valid(&self) -> bool {
+        self.wrap == Wrap::None || self.topology != Topology::Hexagon
+    }
+
+    /// Returns if the size fits the topology.
+    pub(crate) fn is_valid(&self) -> bool {
+        if self.rows <= 0 || self.cols <= 0 {
+            return false;
+        }
+
+        match self.topology {
+            Topology::Square | Topology::Hex => true,
+            Topology::Hexagon => self.rows == self.cols && self.rows % 2 == 1,
+        }
+    }
+
+    /// Returns if the position is a tile of the board.
+    pub(crate) fn contains(&self, pos: Pos) -> bool {
+        let in_rhombus =
+            pos.row() >= 0 && pos.row() < self.rows && pos.col() >= 0 && pos.col() < self.cols;
+
+        match self.topology {
+            Topology::Square | Topology::Hex => in_rhombus,
+            Topology::Hexagon => {
+                let radius = (self.rows - 1) / 2;
+                in_rhombus && (pos.row() + pos.col() - 2 * radius).abs() <= radius
+            }
+        }
+    }
+
     /// Returns the size of each tile.
     pub(crate) const fn tile_size() -> f32 {
         1.0
@@ -0,0 +1,78 @@
+use crate::piece::PieceModel;
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How a moving piece captures other pieces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum CaptureMode {
+    /// Moving onto an occupied tile captures the piece on it (chess).
+    #[default]
+    Replace,
+
+    /// Jumping over enemy pieces on a straight or diagonal line captures them (checkers).
+    ///
+    /// Allies are jumped over unharmed. The target tile must be empty.
+    Jump,
+
+    /// Enemy pieces sandwiched orthogonally between the moved piece and
+    /// another piece of the same team are captured (Hnefatafl).
+    ///
+    /// The target tile must be empty.
+    Custodian,
+
+    /// Targeting an occupied tile captures the piece on it without moving (archer).
+    Ranged,
+}
+
+impl fmt::Display for CaptureMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            CaptureMode::Replace => "Replace",
+            CaptureMode::Jump => "Jump",
+            CaptureMode::Custodian => "Custodian",
+            CaptureMode::Ranged => "Ranged",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// What happens to captured pieces.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub enum CapturePolicy {
+    /// Captured pieces leave the game.
+    #[default]
+    Discard,
+
+    /// Captured pieces go back to their owner's stock, promoted ones as the model they were placed
+    /// as.
+    ReturnToOwner,
+
+    /// Captured pieces join the capturer's hand and can be dropped later (shogi).
+    ///
+    /// Models listed in the map are converted on capture, e.g. promoted pieces
+    /// reverting to their base model. Pieces the capturer cannot use leave the game.
+    Transfer(IndexMap<PieceModel, PieceModel>),
+}
+
+impl CapturePolicy {
+    /// Returns the model a captured piece turns into.
+    pub fn convert(&self, model: PieceModel) -> PieceModel {
+        match self {
+            CapturePolicy::Transfer(map) => map.get(&model).copied().unwrap_or(model),
+            _ => model,
+        }
+    }
+
+    /// Returns all models referenced by the conversion map.
+    pub(crate) fn models(&self) -> impl Iterator<Item = PieceModel> {
+        let map = match self {
+            CapturePolicy::Transfer(map) => Some(map),
+            _ => None,
+        };
+
+        map.into_iter()
+            .flat_map(|map| map.iter().flat_map(|(from, to)| [*from, *to]))
+    }
+}
@@ -0,0 +1,88 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Extra periods a player gets once the main time runs out.
+///
+/// Each period is restored when the player ends a turn within it,
+/// overrunning a period uses it up.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct ByoYomi {
+    /// Number of periods.
+    periods: u32,
+
+    /// Length of each period, in milliseconds.
+    period_millis: u64,
+}
+
+impl ByoYomi {
+    /// Creates new byo-yomi periods.
+    pub fn new(periods: u32, period_millis: u64) -> Self {
+        Self {
+            periods,
+            period_millis,
+        }
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the length of each period, in milliseconds.
+    pub fn period_millis(&self) -> u64 {
+        self.period_millis
+    }
+}
+
+/// Time control of every player.
+///
+/// The remaining time is exposed as `RemainingMillis`, so rules decide what happens
+/// when a player runs out of time, e.g. with a lose condition.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct TimeControl {
+    /// Main time of each player, in milliseconds.
+    initial_millis: u64,
+
+    /// Time added to the main time after each of the player's turns, in milliseconds.
+    #[serde(default)]
+    increment_millis: u64,
+
+    /// Periods used once the main time runs out.
+    #[serde(default)]
+    byo_yomi: Option<ByoYomi>,
+}
+
+impl TimeControl {
+    /// Creates a new time control.
+    pub fn new(initial_millis: u64, increment_millis: u64, byo_yomi: Option<ByoYomi>) -> Self {
+        Self {
+            initial_millis,
+            increment_millis,
+            byo_yomi,
+        }
+    }
+
+    /// Returns the main time of each player, in milliseconds.
+    pub fn initial_millis(&self) -> u64 {
+        self.initial_millis
+    }
+
+    /// Returns the time added after each turn, in milliseconds.
+    pub fn increment_millis(&self) -> u64 {
+        self.increment_millis
+    }
+
+    /// Returns the byo-yomi periods, if any.
+    pub fn byo_yomi(&self) -> Option<&ByoYomi> {
+        self.byo_yomi.as_ref()
+    }
+
+    /// Returns if players get any time at all.
+    pub(crate) fn is_valid(&self) -> bool {
+        let byo_yomi = self
+            .byo_yomi
+            .is_some_and(|byo_yomi| byo_yomi.periods > 0 && byo_yomi.period_millis > 0);
+
+        self.initial_millis > 0 || byo_yomi
+    }
+}
@@ -1,8 +1,9 @@
 use crate::RulesError;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 pub enum Count {
     Infinite,
     Finite(usize),
@@ -32,6 +33,12 @@ impl Count {
         }
     }
 
+    pub fn increase(&mut self) {
+        if let Count::Finite(count) = self {
+            *count += 1;
+        }
+    }
+
     pub fn is_depleted(&self) -> bool {
         match self {
             Count::Infinite => false,
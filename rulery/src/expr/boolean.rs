@@ -6,10 +6,11 @@ use crate::{
     pos::Pos,
     utils::{from_ron_str, to_ron_str},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Boolean expression.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum BoolExpr {
     /// Literal true value.
     True,
@@ -44,17 +45,34 @@ pub enum BoolExpr {
     /// Compare if two colors are equal.
     ColorEqual(Box<ColorExpr>, Box<ColorExpr>),
 
+    /// Query if two colors play on the same team.
+    SameTeam(Box<ColorExpr>, Box<ColorExpr>),
+
     /// Compare if two models are equal.
     ModelEqual(Box<ModelExpr>, Box<ModelExpr>),
 
     /// Query if the given position is occupied by any piece.
     PosOccupied(Box<IntExpr>, Box<IntExpr>),
 
+    /// Query if two hex tiles in axial coordinates share an edge.
+    HexNeighbour((Box<IntExpr>, Box<IntExpr>), (Box<IntExpr>, Box<IntExpr>)),
+
     /// Query if the last action has been performed.
     HasLastAction,
 
+    /// Query if the piece being placed is dropped from the hand (Placement only).
+    #[schemars(extend("x-phases" = ["placement"]))]
+    ToPlaceIsDrop,
+
     /// Query if the player's state is equal to the given state (Game over only).
+    #[schemars(extend("x-phases" = ["game_over"]))]
     PlayerStateEqual(PieceColor, PlayerState),
+
+    /// Query if the team's state is equal to the given state (Game over only).
+    ///
+    /// A player on no declared team is a team of their own, named after their color.
+    #[schemars(extend("x-phases" = ["game_over"]))]
+    TeamStateEqual(String, PlayerState),
 }
 
 impl BoolExpr {
@@ -77,12 +95,72 @@ impl BoolExpr {
             BoolExpr::GreaterOrEqual(lhs, rhs) => Ok(lhs.evaluate(ctx)? >= rhs.evaluate(ctx)?),
             BoolExpr::If(cond, then, otherwise) => Self::conditional(cond, then, otherwise, ctx),
             BoolExpr::ColorEqual(lhs, rhs) => Ok(lhs.evaluate(ctx)? == rhs.evaluate(ctx)?),
+            BoolExpr::SameTeam(lhs, rhs) => ctx.same_team(lhs.evaluate(ctx)?, rhs.evaluate(ctx)?),
             BoolExpr::ModelEqual(lhs, rhs) => Ok(lhs.evaluate(ctx)? == rhs.evaluate(ctx)?),
             BoolExpr::PosOccupied(row, col) => {
                 ctx.pos_occupied(Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?))
             }
+            BoolExpr::HexNeighbour((row1, col1), (row2, col2)) => {
+                let pos1 = Pos::new(row1.evaluate(ctx)?, col1.evaluate(ctx)?);
+                let pos2 = Pos::new(row2.evaluate(ctx)?, col2.evaluate(ctx)?);
+
+                Ok(pos1.hex_distance(pos2) == 1)
+            }
             BoolExpr::HasLastAction => ctx.has_last_action(),
+            BoolExpr::ToPlaceIsDrop => ctx.to_place_is_drop(),
             BoolExpr::PlayerStateEqual(color, state) => ctx.player_state_equal(*color, *state),
+            BoolExpr::TeamStateEqual(team, state) => ctx.team_state_equal(team, *state),
+        }
+    }
+
+    /// Calls `f` on this expression and every boolean expression within it.
+    pub(crate) fn visit_bools<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a BoolExpr),
+    {
+        f(self);
+
+        match self {
+            BoolExpr::And(vec) | BoolExpr::Or(vec) => {
+                for expr in vec {
+                    expr.visit_bools(f);
+                }
+            }
+            BoolExpr::Not(expr) => expr.visit_bools(f),
+            BoolExpr::Equal(lhs, rhs)
+            | BoolExpr::NotEqual(lhs, rhs)
+            | BoolExpr::LessThan(lhs, rhs)
+            | BoolExpr::GreaterThan(lhs, rhs)
+            | BoolExpr::LessOrEqual(lhs, rhs)
+            | BoolExpr::GreaterOrEqual(lhs, rhs)
+            | BoolExpr::PosOccupied(lhs, rhs) => {
+                lhs.visit_bools(f);
+                rhs.visit_bools(f);
+            }
+            BoolExpr::If(cond, then, otherwise) => {
+                cond.visit_bools(f);
+                then.visit_bools(f);
+                otherwise.visit_bools(f);
+            }
+            BoolExpr::ColorEqual(lhs, rhs) | BoolExpr::SameTeam(lhs, rhs) => {
+                lhs.visit_bools(f);
+                rhs.visit_bools(f);
+            }
+            BoolExpr::ModelEqual(lhs, rhs) => {
+                lhs.visit_bools(f);
+                rhs.visit_bools(f);
+            }
+            BoolExpr::HexNeighbour((row1, col1), (row2, col2)) => {
+                for expr in [row1, col1, row2, col2] {
+                    expr.visit_bools(f);
+                }
+            }
+            BoolExpr::True
+            | BoolExpr::False
+            | BoolExpr::HasLastAction
+            | BoolExpr::ToPlaceIsDrop
+            | BoolExpr::PlayerStateEqual(..)
+            | BoolExpr::TeamStateEqual(..) => {}
         }
     }
 
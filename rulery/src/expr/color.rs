@@ -5,10 +5,11 @@ use crate::{
     pos::Pos,
     utils::{from_ron_str, to_ron_str},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Color expression.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ColorExpr {
     /// Literal color value.
     Literal(PieceColor),
@@ -18,14 +19,29 @@ pub enum ColorExpr {
     /// (condition, then, otherwise)
     If(Box<BoolExpr>, Box<ColorExpr>, Box<ColorExpr>),
 
-    /// Query the color of the piece at the given position.
+    /// Query the color of the top piece at the given position.
     ColorAtPos(Box<IntExpr>, Box<IntExpr>),
 
-    /// Query the color of the piece being moved (Movement only).
+    /// Query the color of the piece at the given depth of the stack at the given position.
+    ///
+    /// (row, col, depth), depth 0 is the top piece.
+    ColorAtDepth(Box<IntExpr>, Box<IntExpr>, Box<IntExpr>),
+
+    /// Query the color of the piece being moved (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     MovingColor,
 
     /// Query the color of the piece being placed (Placement only).
+    #[schemars(extend("x-phases" = ["placement"]))]
     ToPlaceColor,
+
+    /// Query the color of the player that may take the next turn (Turn order only).
+    #[schemars(extend("x-phases" = ["turn_order"]))]
+    CandidateColor,
+
+    /// Query the color of the player viewing the piece (Visibility only).
+    #[schemars(extend("x-phases" = ["visibility"]))]
+    ViewerColor,
 }
 
 impl ColorExpr {
@@ -40,8 +56,42 @@ impl ColorExpr {
             ColorExpr::ColorAtPos(row, col) => {
                 ctx.color_at_pos(Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?))
             }
+            ColorExpr::ColorAtDepth(row, col, depth) => ctx.color_at_depth(
+                Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?),
+                depth.evaluate(ctx)?,
+            ),
             ColorExpr::MovingColor => ctx.moving_color(),
             ColorExpr::ToPlaceColor => ctx.to_place_color(),
+            ColorExpr::CandidateColor => ctx.candidate_color(),
+            ColorExpr::ViewerColor => ctx.viewer_color(),
+        }
+    }
+
+    /// Calls `f` on every boolean expression within this expression.
+    pub(crate) fn visit_bools<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a BoolExpr),
+    {
+        match self {
+            ColorExpr::If(cond, then, otherwise) => {
+                cond.visit_bools(f);
+                then.visit_bools(f);
+                otherwise.visit_bools(f);
+            }
+            ColorExpr::ColorAtPos(row, col) => {
+                row.visit_bools(f);
+                col.visit_bools(f);
+            }
+            ColorExpr::ColorAtDepth(row, col, depth) => {
+                row.visit_bools(f);
+                col.visit_bools(f);
+                depth.visit_bools(f);
+            }
+            ColorExpr::Literal(_)
+            | ColorExpr::MovingColor
+            | ColorExpr::ToPlaceColor
+            | ColorExpr::CandidateColor
+            | ColorExpr::ViewerColor => {}
         }
     }
 
@@ -5,10 +5,11 @@ use crate::{
     rect::Rect,
     utils::{from_ron_str, to_ron_str},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Integer expression.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum IntExpr {
     /// A constant integer value.
     Const(i64),
@@ -39,28 +40,92 @@ pub enum IntExpr {
     /// Query the column of the last action.
     LastActionCol,
 
-    /// Query the number of pieces in the given rectangle.
+    /// Query the number of occupied tiles in the given rectangle.
     CountInRect((Box<IntExpr>, Box<IntExpr>), (Box<IntExpr>, Box<IntExpr>)),
-    /// Query the number of pieces with the given model and color in the given rectangle.
+    /// Query the number of pieces in the given rectangle, counting every piece of a stack.
+    CountPiecesInRect((Box<IntExpr>, Box<IntExpr>), (Box<IntExpr>, Box<IntExpr>)),
+    /// Query the number of top pieces with the given model and color in the given rectangle.
     CountPieceInRect(
         (Box<ModelExpr>, Box<ColorExpr>),
         (Box<IntExpr>, Box<IntExpr>),
         (Box<IntExpr>, Box<IntExpr>),
     ),
+    /// Query the number of pieces stacked at the given position.
+    StackHeight(Box<IntExpr>, Box<IntExpr>),
 
-    /// Query the source tile row (Movement only).
+    /// The number of steps between two hex tiles in axial coordinates.
+    HexDistance((Box<IntExpr>, Box<IntExpr>), (Box<IntExpr>, Box<IntExpr>)),
+
+    /// Query the source tile row (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     SourceRow,
-    /// Query the source tile column (Movement only).
+    /// Query the source tile column (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     SourceCol,
-    /// Query the destination tile row (Movement only).
+    /// Query the destination tile row (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     TargetRow,
-    /// Query the destination tile column (Movement only).
+    /// Query the destination tile column (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     TargetCol,
 
     /// Query the row where the piece is being placed (Placement only).
+    #[schemars(extend("x-phases" = ["placement"]))]
     ToPlaceRow,
     /// Query the column where the piece is being placed (Placement only).
+    #[schemars(extend("x-phases" = ["placement"]))]
     ToPlaceCol,
+
+    /// Query the row of the piece that may be captured (Capture only).
+    #[schemars(extend("x-phases" = ["capture"]))]
+    CaptureRow,
+    /// Query the column of the piece that may be captured (Capture only).
+    #[schemars(extend("x-phases" = ["capture"]))]
+    CaptureCol,
+
+    /// Query the row of the piece being viewed (Visibility only).
+    #[schemars(extend("x-phases" = ["visibility"]))]
+    ViewedRow,
+    /// Query the column of the piece being viewed (Visibility only).
+    #[schemars(extend("x-phases" = ["visibility"]))]
+    ViewedCol,
+
+    /// A random integer between min and max, inclusive.
+    ///
+    /// Every `Random` in a condition draws its own integer, checking the condition again within
+    /// the same action repeats them.
+    ///
+    /// (min, max)
+    Random(Box<IntExpr>, Box<IntExpr>),
+    /// Query the dice value rolled at the start of the current turn.
+    DiceValue,
+
+    /// Query the number of turns since the latest capture, or since the game start.
+    TurnsSinceCapture,
+    /// Query the number of turns since the latest placement, or since the game start.
+    TurnsSincePlacement,
+    /// Query the number of turns since a piece of the given model last moved, or since the game start.
+    TurnsSinceMoveOf(Box<ModelExpr>),
+
+    /// Query the time the player with the given color has left, in milliseconds.
+    ///
+    /// Counts the byo-yomi periods left, zero once the player's flag falls.
+    RemainingMillis(Box<ColorExpr>),
+
+    /// Query how many times the position reached by the last action has occurred, including now.
+    ///
+    /// A position is the placed pieces, every player's stock and hand, and the player to act next
+    /// with the actions they have left, e.g. `GreaterOrEqual(RepetitionCount, Const(3))`
+    /// for a threefold repetition draw.
+    ///
+    /// Positions are recorded when an action ends, not when a flag falls, so conditions evaluated
+    /// during an action, such as movement, see the position before it and cannot forbid a
+    /// repeating move.
+    RepetitionCount,
+
+    /// Query the score of the player with the given color (Game over only).
+    #[schemars(extend("x-phases" = ["game_over"]))]
+    ScoreOf(Box<ColorExpr>),
 }
 
 impl IntExpr {
@@ -84,15 +149,96 @@ impl IntExpr {
             IntExpr::CountInRect((row1, col1), (row2, col2)) => {
                 Self::count_in_rect((row1, col1), (row2, col2), ctx)
             }
+            IntExpr::CountPiecesInRect((row1, col1), (row2, col2)) => {
+                Self::count_pieces_in_rect((row1, col1), (row2, col2), ctx)
+            }
             IntExpr::CountPieceInRect((model, color), (row1, col1), (row2, col2)) => {
                 Self::count_piece_in_rect((model, color), (row1, col1), (row2, col2), ctx)
             }
+            IntExpr::StackHeight(row, col) => {
+                ctx.stack_height(Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?))
+            }
+            IntExpr::HexDistance((row1, col1), (row2, col2)) => {
+                Self::hex_distance((row1, col1), (row2, col2), ctx)
+            }
             IntExpr::SourceRow => ctx.source_row(),
             IntExpr::SourceCol => ctx.source_col(),
             IntExpr::TargetRow => ctx.target_row(),
             IntExpr::TargetCol => ctx.target_col(),
             IntExpr::ToPlaceRow => ctx.to_place_row(),
             IntExpr::ToPlaceCol => ctx.to_place_col(),
+            IntExpr::CaptureRow => ctx.capture_row(),
+            IntExpr::CaptureCol => ctx.capture_col(),
+            IntExpr::ViewedRow => ctx.viewed_row(),
+            IntExpr::ViewedCol => ctx.viewed_col(),
+            IntExpr::Random(min, max) => ctx.random(min.evaluate(ctx)?, max.evaluate(ctx)?),
+            IntExpr::DiceValue => ctx.dice_value(),
+            IntExpr::TurnsSinceCapture => ctx.turns_since_capture(),
+            IntExpr::TurnsSincePlacement => ctx.turns_since_placement(),
+            IntExpr::TurnsSinceMoveOf(model) => ctx.turns_since_move_of(model.evaluate(ctx)?),
+            IntExpr::RemainingMillis(color) => ctx.remaining_millis(color.evaluate(ctx)?),
+            IntExpr::RepetitionCount => ctx.repetition_count(),
+            IntExpr::ScoreOf(color) => ctx.score_of(color.evaluate(ctx)?),
+        }
+    }
+
+    /// Calls `f` on every boolean expression within this expression.
+    pub(crate) fn visit_bools<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a BoolExpr),
+    {
+        match self {
+            IntExpr::Add(lhs, rhs)
+            | IntExpr::Sub(lhs, rhs)
+            | IntExpr::Mul(lhs, rhs)
+            | IntExpr::Div(lhs, rhs)
+            | IntExpr::StackHeight(lhs, rhs)
+            | IntExpr::Random(lhs, rhs) => {
+                lhs.visit_bools(f);
+                rhs.visit_bools(f);
+            }
+            IntExpr::Abs(expr) => expr.visit_bools(f),
+            IntExpr::If(cond, then, otherwise) => {
+                cond.visit_bools(f);
+                then.visit_bools(f);
+                otherwise.visit_bools(f);
+            }
+            IntExpr::CountInRect((row1, col1), (row2, col2))
+            | IntExpr::CountPiecesInRect((row1, col1), (row2, col2))
+            | IntExpr::HexDistance((row1, col1), (row2, col2)) => {
+                for expr in [row1, col1, row2, col2] {
+                    expr.visit_bools(f);
+                }
+            }
+            IntExpr::CountPieceInRect((model, color), (row1, col1), (row2, col2)) => {
+                model.visit_bools(f);
+                color.visit_bools(f);
+
+                for expr in [row1, col1, row2, col2] {
+                    expr.visit_bools(f);
+                }
+            }
+            IntExpr::TurnsSinceMoveOf(model) => model.visit_bools(f),
+            IntExpr::RemainingMillis(color) | IntExpr::ScoreOf(color) => color.visit_bools(f),
+            IntExpr::Const(_)
+            | IntExpr::TurnNumber
+            | IntExpr::RoundNumber
+            | IntExpr::LastActionRow
+            | IntExpr::LastActionCol
+            | IntExpr::SourceRow
+            | IntExpr::SourceCol
+            | IntExpr::TargetRow
+            | IntExpr::TargetCol
+            | IntExpr::ToPlaceRow
+            | IntExpr::ToPlaceCol
+            | IntExpr::CaptureRow
+            | IntExpr::CaptureCol
+            | IntExpr::ViewedRow
+            | IntExpr::ViewedCol
+            | IntExpr::DiceValue
+            | IntExpr::TurnsSinceCapture
+            | IntExpr::TurnsSincePlacement
+            | IntExpr::RepetitionCount => {}
         }
     }
 
@@ -121,6 +267,20 @@ impl IntExpr {
         }
     }
 
+    fn hex_distance<C>(
+        pos1: (&IntExpr, &IntExpr),
+        pos2: (&IntExpr, &IntExpr),
+        ctx: &C,
+    ) -> Result<i64, C::Error>
+    where
+        C: Context,
+    {
+        let pos1 = Pos::new(pos1.0.evaluate(ctx)?, pos1.1.evaluate(ctx)?);
+        let pos2 = Pos::new(pos2.0.evaluate(ctx)?, pos2.1.evaluate(ctx)?);
+
+        Ok(pos1.hex_distance(pos2))
+    }
+
     fn count_in_rect<C>(
         pos1: (&IntExpr, &IntExpr),
         pos2: (&IntExpr, &IntExpr),
@@ -135,6 +295,20 @@ impl IntExpr {
         ))
     }
 
+    fn count_pieces_in_rect<C>(
+        pos1: (&IntExpr, &IntExpr),
+        pos2: (&IntExpr, &IntExpr),
+        ctx: &C,
+    ) -> Result<i64, C::Error>
+    where
+        C: Context,
+    {
+        ctx.count_pieces_in_rect(Rect::new(
+            Pos::new(pos1.0.evaluate(ctx)?, pos1.1.evaluate(ctx)?),
+            Pos::new(pos2.0.evaluate(ctx)?, pos2.1.evaluate(ctx)?),
+        ))
+    }
+
     fn count_piece_in_rect<C>(
         piece: (&ModelExpr, &ColorExpr),
         pos1: (&IntExpr, &IntExpr),
@@ -37,22 +37,64 @@ pub trait Context {
     /// If no last action has been performed, return an error.
     fn last_action_col(&self) -> Result<i64, Self::Error>;
 
-    /// Query the number of pieces in a rectangle defined by two positions.
+    /// Query the number of occupied tiles in a rectangle defined by two positions.
     fn count_in_rect(&self, rect: Rect) -> Result<i64, Self::Error>;
 
-    /// Query the number of pieces of a specific model and color in a rectangle defined by two positions.
+    /// Query the number of pieces in a rectangle defined by two positions, including those under the top of a stack.
+    fn count_pieces_in_rect(&self, rect: Rect) -> Result<i64, Self::Error>;
+
+    /// Query the number of top pieces of a specific model and color in a rectangle defined by two positions.
     fn count_piece_in_rect(
         &self,
         piece: (PieceModel, PieceColor),
         rect: Rect,
     ) -> Result<i64, Self::Error>;
 
-    /// Query the model of the piece at a specific position.
+    /// Draw a random integer between min and max, inclusive, from the game's seeded generator.
+    fn random(&self, min: i64, max: i64) -> Result<i64, Self::Error>;
+
+    /// Query the dice value rolled at the start of the current turn.
+    fn dice_value(&self) -> Result<i64, Self::Error>;
+
+    /// Query the number of turns since the latest capture, or since the game start.
+    fn turns_since_capture(&self) -> Result<i64, Self::Error>;
+
+    /// Query the number of turns since the latest placement, or since the game start.
+    fn turns_since_placement(&self) -> Result<i64, Self::Error>;
+
+    /// Query the number of turns since a piece of the model last moved, or since the game start.
+    fn turns_since_move_of(&self, model: PieceModel) -> Result<i64, Self::Error>;
+
+    /// Query the time the player with the given color has left, in milliseconds.
+    ///
+    /// If the game is untimed, return an error.
+    fn remaining_millis(&self, color: PieceColor) -> Result<i64, Self::Error>;
+
+    /// Query how many times the current position has occurred, including now.
+    fn repetition_count(&self) -> Result<i64, Self::Error>;
+
+    /// Query whether two colors play on the same team.
+    fn same_team(&self, a: PieceColor, b: PieceColor) -> Result<bool, Self::Error>;
+
+    /// Query the model of the top piece at a specific position.
     fn model_at_pos(&self, pos: Pos) -> Result<PieceModel, Self::Error>;
 
-    /// Query the color of the piece at a specific position.
+    /// Query the color of the top piece at a specific position.
     fn color_at_pos(&self, pos: Pos) -> Result<PieceColor, Self::Error>;
 
+    /// Query the number of pieces stacked at a specific position.
+    fn stack_height(&self, pos: Pos) -> Result<i64, Self::Error>;
+
+    /// Query the model of the piece at a depth of the stack at a specific position.
+    ///
+    /// Depth 0 is the top piece.
+    fn model_at_depth(&self, pos: Pos, depth: i64) -> Result<PieceModel, Self::Error>;
+
+    /// Query the color of the piece at a depth of the stack at a specific position.
+    ///
+    /// Depth 0 is the top piece.
+    fn color_at_depth(&self, pos: Pos, depth: i64) -> Result<PieceColor, Self::Error>;
+
     /// Query the model of the piece being moved.
     ///
     /// Only support in movement.
@@ -123,6 +165,55 @@ pub trait Context {
         Err(RulesError::UnsupportedVariable.into())
     }
 
+    /// Query whether the piece to place is dropped from the hand rather than taken from stock.
+    ///
+    /// Only support in placement.
+    fn to_place_is_drop(&self) -> Result<bool, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the row of the piece that may be captured.
+    ///
+    /// Only support in capture.
+    fn capture_row(&self) -> Result<i64, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the column of the piece that may be captured.
+    ///
+    /// Only support in capture.
+    fn capture_col(&self) -> Result<i64, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the color of the player that may take the next turn.
+    ///
+    /// Only support in turn order.
+    fn candidate_color(&self) -> Result<PieceColor, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the row of the piece being viewed.
+    ///
+    /// Only support in visibility.
+    fn viewed_row(&self) -> Result<i64, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the column of the piece being viewed.
+    ///
+    /// Only support in visibility.
+    fn viewed_col(&self) -> Result<i64, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the color of the player viewing the piece.
+    ///
+    /// Only support in visibility.
+    fn viewer_color(&self) -> Result<PieceColor, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
     /// Query the state of the player with the given color.
     ///
     /// Only support in game over.
@@ -133,4 +224,18 @@ pub trait Context {
     ) -> Result<bool, Self::Error> {
         Err(RulesError::UnsupportedVariable.into())
     }
+
+    /// Query the state of the team with the given name.
+    ///
+    /// Only support in game over.
+    fn team_state_equal(&self, _team: &str, _state: PlayerState) -> Result<bool, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
+
+    /// Query the score of the player with the given color.
+    ///
+    /// Only support in game over.
+    fn score_of(&self, _color: PieceColor) -> Result<i64, Self::Error> {
+        Err(RulesError::UnsupportedVariable.into())
+    }
 }
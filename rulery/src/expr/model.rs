@@ -5,10 +5,11 @@ use crate::{
     pos::Pos,
     utils::{from_ron_str, to_ron_str},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Model expression.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ModelExpr {
     /// Literal model value.
     Literal(PieceModel),
@@ -18,13 +19,20 @@ pub enum ModelExpr {
     /// (condition, then, otherwise)
     If(Box<BoolExpr>, Box<ModelExpr>, Box<ModelExpr>),
 
-    /// Query the model of the piece at the given position.
+    /// Query the model of the top piece at the given position.
     ModelAtPos(Box<IntExpr>, Box<IntExpr>),
 
-    /// Query the model of the piece being moved (Movement only).
+    /// Query the model of the piece at the given depth of the stack at the given position.
+    ///
+    /// (row, col, depth), depth 0 is the top piece.
+    ModelAtDepth(Box<IntExpr>, Box<IntExpr>, Box<IntExpr>),
+
+    /// Query the model of the piece being moved (Movement and capture only).
+    #[schemars(extend("x-phases" = ["movement", "capture"]))]
     MovingModel,
 
     /// Query the model of the piece being placed (Placement only).
+    #[schemars(extend("x-phases" = ["placement"]))]
     ToPlaceModel,
 }
 
@@ -40,11 +48,39 @@ impl ModelExpr {
             ModelExpr::ModelAtPos(row, col) => {
                 ctx.model_at_pos(Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?))
             }
+            ModelExpr::ModelAtDepth(row, col, depth) => ctx.model_at_depth(
+                Pos::new(row.evaluate(ctx)?, col.evaluate(ctx)?),
+                depth.evaluate(ctx)?,
+            ),
             ModelExpr::MovingModel => ctx.moving_model(),
             ModelExpr::ToPlaceModel => ctx.to_place_model(),
         }
     }
 
+    /// Calls `f` on every boolean expression within this expression.
+    pub(crate) fn visit_bools<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a BoolExpr),
+    {
+        match self {
+            ModelExpr::If(cond, then, otherwise) => {
+                cond.visit_bools(f);
+                then.visit_bools(f);
+                otherwise.visit_bools(f);
+            }
+            ModelExpr::ModelAtPos(row, col) => {
+                row.visit_bools(f);
+                col.visit_bools(f);
+            }
+            ModelExpr::ModelAtDepth(row, col, depth) => {
+                row.visit_bools(f);
+                col.visit_bools(f);
+                depth.visit_bools(f);
+            }
+            ModelExpr::Literal(_) | ModelExpr::MovingModel | ModelExpr::ToPlaceModel => {}
+        }
+    }
+
     /// Parses from a ron string.
     pub fn from_ron_str(str: &str) -> Result<Self, RulesError> {
         from_ron_str(str)
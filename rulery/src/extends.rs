@@ -0,0 +1,293 @@
+//! Rule variants that inherit a base rules file.
+//!
+//! A variant sets `extends` to its base, relative to itself, and declares only what it changes.
+//! Top-level fields replace the inherited ones, except for:
+//!
+//! - `board`, whose fields are replaced one by one,
+//! - `pieces`, whose fields are replaced one by one for each declared kind,
+//! - `players`, whose rules are replaced for each declared player,
+//! - `initial_layout`, whose pieces replace the inherited ones on the same positions,
+//! - `include`, whose files are merged on top of the base, replacing the pieces and players
+//!   they redeclare, before the variant's own `pieces` and `players` apply.
+//!
+//! `time_control: None` removes the inherited time control.
+//!
+//! A base may extend another base, the chain must not loop.
+
+use crate::{
+    GameRulesInner, RulesError,
+    board::{Topology, Wrap},
+    capture::CapturePolicy,
+    clock::TimeControl,
+    expr::boolean::BoolExpr,
+    format::Format,
+    include::{include_over_base, resolve_includes},
+    initial_layout::InitialPiece,
+    locale::LocalizedText,
+    metadata::Metadata,
+    migration::parse_current,
+    piece::{PieceColor, PieceModel, PieceOverrides},
+    player::{PlayerRules, TieBreak},
+    stack::StackMode,
+    team::TeamRuleSet,
+    turn::TurnSchedule,
+    utils::deserialize_some,
+};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    fs, mem,
+    path::{Path, PathBuf},
+};
+
+/// The base of a variant and the top-level fields it declares.
+#[derive(Debug, Clone)]
+pub(crate) struct Inheritance {
+    /// The base rules file, relative to the variant.
+    pub(crate) base: PathBuf,
+
+    /// Names of the fields declared by the variant.
+    pub(crate) overridden: HashSet<&'static str>,
+}
+
+/// Only the base of a rules file, other fields are ignored.
+#[derive(Debug, Deserialize)]
+struct Extends {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    extends: Option<PathBuf>,
+}
+
+/// Changes a variant makes to its base, absent fields are inherited.
+///
+/// Fields are written as in a complete rules file.
+#[derive(Debug, Deserialize)]
+struct Variant {
+    #[serde(default)]
+    include: Vec<PathBuf>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    name: Option<LocalizedText>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    metadata: Option<Metadata>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    board: Option<BoardOverrides>,
+
+    #[serde(default)]
+    pieces: IndexMap<PieceModel, PieceOverrides>,
+
+    #[serde(default)]
+    players: IndexMap<PieceColor, PlayerRules>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    teams: Option<TeamRuleSet>,
+
+    #[serde(default)]
+    initial_layout: Vec<InitialPiece>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    capture_policy: Option<CapturePolicy>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    turn_schedule: Option<TurnSchedule>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    time_control: Option<Option<TimeControl>>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    stacking: Option<StackMode>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    draw_condition: Option<BoolExpr>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    tie_break: Option<TieBreak>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    game_over_condition: Option<BoolExpr>,
+}
+
+/// Changes a variant makes to the board, absent fields are inherited.
+#[derive(Debug, Deserialize)]
+struct BoardOverrides {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    rows: Option<i64>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    cols: Option<i64>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    topology: Option<Topology>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    wrap: Option<Wrap>,
+}
+
+impl Variant {
+    /// Returns the names of the declared fields.
+    fn overridden(&self) -> HashSet<&'static str> {
+        [
+            ("name", self.name.is_some()),
+            ("metadata", self.metadata.is_some()),
+            ("board", self.board.is_some()),
+            ("pieces", !self.pieces.is_empty()),
+            ("players", !self.players.is_empty()),
+            ("teams", self.teams.is_some()),
+            ("initial_layout", !self.initial_layout.is_empty()),
+            ("capture_policy", self.capture_policy.is_some()),
+            ("turn_schedule", self.turn_schedule.is_some()),
+            ("time_control", self.time_control.is_some()),
+            ("stacking", self.stacking.is_some()),
+            ("draw_condition", self.draw_condition.is_some()),
+            ("tie_break", self.tie_break.is_some()),
+            ("game_over_condition", self.game_over_condition.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(field, declared)| declared.then_some(field))
+        .collect()
+    }
+
+    /// Applies the changes to the base rules, `path` is the variant file.
+    fn apply(self, rules: &mut GameRulesInner, path: &Path) -> Result<(), RulesError> {
+        if let Some(name) = self.name {
+            rules.name = name;
+        }
+
+        if let Some(metadata) = self.metadata {
+            rules.metadata = metadata;
+        }
+
+        if let Some(board) = self.board {
+            if let Some(rows) = board.rows {
+                rules.board.set_rows(rows);
+            }
+
+            if let Some(cols) = board.cols {
+                rules.board.set_cols(cols);
+            }
+
+            if let Some(topology) = board.topology {
+                rules.board.set_topology(topology);
+            }
+
+            if let Some(wrap) = board.wrap {
+                rules.board.set_wrap(wrap);
+            }
+        }
+
+        for (model, overrides) in self.pieces {
+            rules.pieces.apply(model, overrides, path)?;
+        }
+
+        for (color, player) in self.players {
+            rules.players.set(color, player);
+        }
+
+        if let Some(teams) = self.teams {
+            rules.teams = teams;
+        }
+
+        rules.initial_layout.replace(self.initial_layout);
+
+        if let Some(capture_policy) = self.capture_policy {
+            rules.capture_policy = capture_policy;
+        }
+
+        if let Some(turn_schedule) = self.turn_schedule {
+            rules.turn_schedule = turn_schedule;
+        }
+
+        if let Some(time_control) = self.time_control {
+            rules.time_control = time_control;
+        }
+
+        if let Some(stacking) = self.stacking {
+            rules.stacking = stacking;
+        }
+
+        if let Some(draw_condition) = self.draw_condition {
+            rules.draw_condition = draw_condition;
+        }
+
+        if let Some(tie_break) = self.tie_break {
+            rules.tie_break = tie_break;
+        }
+
+        if let Some(game_over_condition) = self.game_over_condition {
+            rules.game_over_condition = game_over_condition;
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads the rules from `path`, resolving its bases and included files.
+pub(crate) fn load_rules(path: &Path, format: Format) -> Result<GameRulesInner, RulesError> {
+    Loader::default().load(path, format)
+}
+
+#[derive(Default)]
+struct Loader {
+    /// Canonical paths of the files loaded so far, from the variant to its bases.
+    chain: Vec<PathBuf>,
+}
+
+impl Loader {
+    fn load(&mut self, path: &Path, format: Format) -> Result<GameRulesInner, RulesError> {
+        let canonical = fs::canonicalize(path)?;
+
+        if self.chain.contains(&canonical) {
+            return Err(RulesError::ExtendsCycle(path.to_path_buf()));
+        }
+
+        self.chain.push(canonical);
+
+        let str = fs::read_to_string(path)?;
+        let Extends { extends } = parse_current(&str, format)?;
+
+        let mut rules = match extends {
+            Some(base) => self.load_variant(path, &str, format, base)?,
+            None => parse_current(&str, format)?,
+        };
+
+        // Asset files are relative to the rules file.
+        rules.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        resolve_includes(&mut rules, path)?;
+
+        Ok(rules)
+    }
+
+    fn load_variant(
+        &mut self,
+        path: &Path,
+        str: &str,
+        format: Format,
+        base: PathBuf,
+    ) -> Result<GameRulesInner, RulesError> {
+        let mut variant: Variant = parse_current(str, format)?;
+
+        let base_path = path.parent().unwrap_or(Path::new("")).join(&base);
+        let mut rules = self
+            .load(&base_path, Format::from_path(&base_path))
+            .map_err(|err| RulesError::InFile(base_path.clone(), Box::new(err)))?;
+
+        // Inherited asset files stay relative to the base.
+        rules
+            .pieces
+            .rebase_files(base.parent().unwrap_or(Path::new("")));
+
+        let overridden = variant.overridden();
+
+        // The base's files are already merged, the variant's go on top before its own changes.
+        include_over_base(&mut rules, mem::take(&mut variant.include), path)?;
+
+        variant.apply(&mut rules, path)?;
+
+        rules.inheritance = Some(Inheritance { base, overridden });
+
+        Ok(rules)
+    }
+}
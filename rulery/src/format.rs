@@ -0,0 +1,87 @@
+use crate::{
+    RulesError, UncheckedGameRules,
+    utils::{from_ron_str, to_ron_str},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use std::path::Path;
+
+/// A file format for game rules.
+///
+/// All formats describe the same data model, so rules convert between them losslessly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Ron,
+    Json,
+    Toml,
+}
+
+impl Format {
+    /// Detects the format from a file extension, RON if unknown.
+    pub fn from_path<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let extension = path.as_ref().extension().and_then(|ext| ext.to_str());
+
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => Format::Json,
+            Some("toml") => Format::Toml,
+            _ => Format::Ron,
+        }
+    }
+
+    /// Returns the file extension of the format.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Format::Ron => "ron",
+            Format::Json => "json",
+            Format::Toml => "toml",
+        }
+    }
+
+    /// Parses a value.
+    pub(crate) fn parse<T>(self, str: &str) -> Result<T, RulesError>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Format::Ron => from_ron_str(str),
+            Format::Json => Ok(serde_json::from_str(str)?),
+            Format::Toml => Ok(toml::from_str(str)?),
+        }
+    }
+
+    /// Converts a value into a string.
+    pub(crate) fn write<T>(self, value: &T) -> Result<String, RulesError>
+    where
+        T: Serialize,
+    {
+        match self {
+            Format::Ron => to_ron_str(value),
+            Format::Json => Ok(serde_json::to_string_pretty(value)?),
+            Format::Toml => Ok(toml::to_string_pretty(value)?),
+        }
+    }
+}
+
+/// Translates game rules from one format into another.
+///
+/// The rules are not checked, comments and formatting are not kept.
+pub fn convert(str: &str, from: Format, to: Format) -> Result<String, RulesError> {
+    let rules = UncheckedGameRules::parse(str, from)?;
+
+    to.write(&rules)
+}
+
+/// Translates a game rules file into another, with the formats detected from their extensions.
+pub fn convert_file<P, Q>(from: P, to: Q) -> Result<(), RulesError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let str = std::fs::read_to_string(&from)?;
+    let converted = convert(&str, Format::from_path(&from), Format::from_path(&to))?;
+
+    Ok(std::fs::write(to, converted)?)
+}
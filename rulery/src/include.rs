@@ -0,0 +1,138 @@
+//! Piece and player libraries shared between rules files.
+//!
+//! A rules file lists other files in `include`, with paths relative to itself.
+//! Their pieces and players are merged before the file's own, in order.
+//! Included files may include others, each file is merged once.
+
+use crate::{
+    GameRulesInner, RulesError, format::Format, migration::parse_current, piece::PieceRuleSet,
+    player::PlayerRuleSet,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    fs, mem,
+    path::{Path, PathBuf},
+};
+
+/// The parts of an included file that are merged, other fields are ignored.
+#[derive(Debug, Deserialize)]
+struct Library {
+    #[serde(default)]
+    include: Vec<PathBuf>,
+
+    #[serde(default = "PieceRuleSet::new")]
+    pieces: PieceRuleSet,
+
+    #[serde(default = "PlayerRuleSet::new")]
+    players: PlayerRuleSet,
+}
+
+/// Merges the included files into the rules loaded from `path`.
+pub(crate) fn resolve_includes(rules: &mut GameRulesInner, path: &Path) -> Result<(), RulesError> {
+    if rules.include.is_empty() {
+        return Ok(());
+    }
+
+    let mut merger = Merger::new(rules.base_dir.clone(), path)?;
+
+    for include in mem::take(&mut rules.include) {
+        merger.include(&include)?;
+    }
+
+    // The file's own declarations come last.
+    let in_file = |err| RulesError::InFile(path.to_path_buf(), Box::new(err));
+
+    merger
+        .pieces
+        .merge(mem::replace(&mut rules.pieces, PieceRuleSet::new()))
+        .map_err(in_file)?;
+
+    merger
+        .players
+        .merge(mem::replace(&mut rules.players, PlayerRuleSet::new()))
+        .map_err(in_file)?;
+
+    rules.pieces = merger.pieces;
+    rules.players = merger.players;
+
+    Ok(())
+}
+
+/// Merges the files included by the variant at `path` on top of the rules it inherits.
+///
+/// Pieces and players declared by the included files replace the inherited ones.
+pub(crate) fn include_over_base(
+    rules: &mut GameRulesInner,
+    include: Vec<PathBuf>,
+    path: &Path,
+) -> Result<(), RulesError> {
+    if include.is_empty() {
+        return Ok(());
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    let mut merger = Merger::new(dir, path)?;
+
+    for include in include {
+        merger.include(&include)?;
+    }
+
+    rules.pieces.replace(merger.pieces);
+    rules.players.replace(merger.players);
+
+    Ok(())
+}
+
+struct Merger {
+    /// Directory of the including rules file.
+    base_dir: PathBuf,
+
+    /// Canonical paths of the files merged so far.
+    visited: HashSet<PathBuf>,
+
+    pieces: PieceRuleSet,
+    players: PlayerRuleSet,
+}
+
+impl Merger {
+    fn new(base_dir: PathBuf, path: &Path) -> Result<Self, RulesError> {
+        Ok(Self {
+            base_dir,
+            visited: HashSet::from([fs::canonicalize(path)?]),
+            pieces: PieceRuleSet::new(),
+            players: PlayerRuleSet::new(),
+        })
+    }
+
+    /// Merges a file, with its path relative to the including rules file.
+    fn include(&mut self, path: &Path) -> Result<(), RulesError> {
+        let full_path = self.base_dir.join(path);
+        let in_file = |err| RulesError::InFile(full_path.clone(), Box::new(err));
+
+        if !self
+            .visited
+            .insert(fs::canonicalize(&full_path).map_err(|err| in_file(err.into()))?)
+        {
+            return Ok(());
+        }
+
+        let str = fs::read_to_string(&full_path).map_err(|err| in_file(err.into()))?;
+        let mut library: Library =
+            parse_current(&str, Format::from_path(&full_path)).map_err(in_file)?;
+
+        // Nested includes are relative to the included file.
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        for include in mem::take(&mut library.include) {
+            self.include(&dir.join(include))?;
+        }
+
+        library.pieces.rebase_files(dir);
+
+        self.pieces.merge(library.pieces).map_err(in_file)?;
+        self.players.merge(library.players).map_err(in_file)?;
+
+        Ok(())
+    }
+}
@@ -4,9 +4,10 @@ use crate::{
     pos::Pos,
     utils::{from_ron_str, to_ron_str},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InitialPiece {
     model: PieceModel,
     color: PieceColor,
@@ -39,7 +40,7 @@ impl InitialPiece {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
 pub(crate) struct InitialLayout(Vec<InitialPiece>);
 
@@ -53,6 +54,13 @@ impl InitialLayout {
         self.0.push(piece);
     }
 
+    /// Replaces the pieces at the positions of the given ones, adding the others.
+    pub(crate) fn replace(&mut self, pieces: Vec<InitialPiece>) {
+        self.0
+            .retain(|piece| !pieces.iter().any(|other| other.pos == piece.pos));
+        self.0.extend(pieces);
+    }
+
     /// Returns the pieces.
     pub(crate) fn pieces(&self) -> impl Iterator<Item = &InitialPiece> {
         self.0.iter()
@@ -1,40 +1,67 @@
 use crate::{
-    board::BoardRuleSet,
+    board::{BoardRuleSet, BoardWrap, Topology, Wrap},
+    capture::CapturePolicy,
+    clock::TimeControl,
     count::Count,
     expr::{Context, boolean::BoolExpr},
+    extends::{Inheritance, load_rules},
+    format::Format,
     initial_layout::{InitialLayout, InitialPiece},
-    piece::{PieceColor, PieceModel, PieceRuleSet, PieceRules},
-    player::{PlayerRuleSet, PlayerRules},
+    locale::LocalizedText,
+    metadata::Metadata,
+    migration::{CURRENT_VERSION, parse_current},
+    piece::{BuiltinColor, BuiltinModel, PieceColor, PieceModel, PieceRuleSet, PieceRules, Rgb},
+    player::{PlayerRuleSet, PlayerRules, TieBreak},
     pos::Pos,
-    utils::{from_ron_file, to_ron_file},
+    stack::StackMode,
+    team::TeamRuleSet,
+    turn::TurnSchedule,
+    utils::is_valid_name,
 };
 use ron::de::SpannedError;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    path::Path,
+    fs,
+    path::{Path, PathBuf},
 };
 use thiserror::Error;
 
 pub mod board;
+pub mod capture;
+pub mod clock;
 pub mod count;
 pub mod expr;
+pub mod format;
 pub mod initial_layout;
+pub mod locale;
+pub mod metadata;
+pub mod migration;
 pub mod piece;
 pub mod player;
 pub mod pos;
+pub mod promotion;
 pub mod rect;
+pub mod schema;
+pub mod stack;
+pub mod team;
+pub mod turn;
 
+mod extends;
+mod include;
 mod utils;
 
 #[derive(Debug, Error)]
 pub enum RulesError {
     #[error("invalid board size")]
     InvalidBoardSize,
-    #[error("duplicate piece color")]
-    DuplicateColor,
-    #[error("duplicate piece model")]
-    DuplicateModel,
+    #[error("board topology cannot wrap")]
+    InvalidWrap,
+    #[error("duplicate piece color: {0}")]
+    DuplicateColor(PieceColor),
+    #[error("duplicate piece model: {0}")]
+    DuplicateModel(PieceModel),
     #[error("no such piece color: {0}")]
     NoSuchColor(PieceColor),
     #[error("no such piece model: {0}")]
@@ -45,6 +72,20 @@ pub enum RulesError {
     NoAddedPiece,
     #[error("no added player")]
     NoAddedPlayer,
+    #[error("no such team: {0}")]
+    NoSuchTeam(String),
+    #[error("player in more than one team: {0}")]
+    DuplicateTeamMember(PieceColor),
+    #[error("invalid actions per turn")]
+    InvalidActionsPerTurn,
+    #[error("invalid dice")]
+    InvalidDice,
+    #[error("invalid time control")]
+    InvalidTimeControl,
+    #[error("invalid name: {0}")]
+    InvalidName(String),
+    #[error("no promotion model for: {0}")]
+    NoPromotionModel(PieceModel),
     #[error("division by zero")]
     DivisionByZero,
     #[error("initial piece position out of board: {0}")]
@@ -59,33 +100,99 @@ pub enum RulesError {
     UnsupportedVariable,
     #[error("piece count is depleted")]
     CountDepleted,
+    #[error("unsupported rules version: {0}")]
+    UnsupportedVersion(u32),
     #[error("format error: {0}")]
     Format(#[from] SpannedError),
     #[error("ron error: {0}")]
     Ron(#[from] ron::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("toml error: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("toml error: {0}")]
+    TomlSer(#[from] toml::ser::Error),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("rules extend themselves: {}", .0.display())]
+    ExtendsCycle(PathBuf),
+    #[error("{path}: {err}", path = .0.display(), err = .1)]
+    InFile(PathBuf, Box<RulesError>),
+    #[error("{path}: new piece model needs count, movement and placement: {model}", path = .0.display(), model = .1)]
+    IncompletePiece(PathBuf, PieceModel),
 }
 
 /// Unchecked game rules.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct UncheckedGameRules(GameRulesInner);
 
 impl UncheckedGameRules {
-    /// Loads game rules from a file.
+    /// Loads game rules from a file, with the format detected from its extension.
+    ///
+    /// RON written in older versions of the format is upgraded first.
     pub fn load<P>(path: P) -> Result<Self, RulesError>
     where
         P: AsRef<Path>,
     {
-        from_ron_file(path)
+        let format = Format::from_path(&path);
+        Self::load_as(path, format)
     }
 
-    /// Sets the name of the game rules.
-    pub fn set_name(&mut self, name: impl Into<String>) {
+    /// Loads game rules from a JSON file.
+    ///
+    /// Unlike RON, JSON is not upgraded and must be of the current version.
+    pub fn load_json<P>(path: P) -> Result<Self, RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_as(path, Format::Json)
+    }
+
+    /// Loads game rules from a TOML file.
+    ///
+    /// Unlike RON, TOML is not upgraded and must be of the current version.
+    pub fn load_toml<P>(path: P) -> Result<Self, RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_as(path, Format::Toml)
+    }
+
+    /// Parses game rules in the specified format.
+    ///
+    /// RON written in older versions of the format is upgraded first,
+    /// JSON and TOML must be of the current version.
+    ///
+    /// Included files and the bases of variants are only resolved when loading from a file.
+    pub fn parse(str: &str, format: Format) -> Result<Self, RulesError> {
+        parse_current(str, format)
+    }
+
+    fn load_as<P>(path: P, format: Format) -> Result<Self, RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        load_rules(path.as_ref(), format).map(Self)
+    }
+
+    /// Sets the name of the game rules, in one or more languages.
+    pub fn set_name(&mut self, name: impl Into<LocalizedText>) {
         self.0.name = name.into();
     }
 
+    /// Sets the description, authors and other information shown to players.
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        self.0.metadata = metadata;
+    }
+
+    /// Selects the language of the texts shown to players, e.g. `zh` or `ja-JP`.
+    ///
+    /// Texts missing in that language fall back to another region, then to the first language.
+    pub fn set_locale(&mut self, locale: impl Into<String>) {
+        self.0.locale = locale.into();
+    }
+
     /// Sets the number of board rows.
     pub const fn set_board_rows(&mut self, num: i64) {
         self.0.board.set_rows(num);
@@ -96,6 +203,16 @@ impl UncheckedGameRules {
         self.0.board.set_cols(num);
     }
 
+    /// Sets the board topology.
+    pub const fn set_board_topology(&mut self, topology: Topology) {
+        self.0.board.set_topology(topology);
+    }
+
+    /// Sets which board axes wrap around.
+    pub const fn set_board_wrap(&mut self, wrap: Wrap) {
+        self.0.board.set_wrap(wrap);
+    }
+
     /// Adds a new piece model with its rules.
     pub fn add_piece(&mut self, model: PieceModel, rules: PieceRules) -> Result<(), RulesError> {
         self.0.pieces.add(model, rules)
@@ -120,6 +237,13 @@ impl UncheckedGameRules {
         Ok(())
     }
 
+    /// Parses teams from a ron string.
+    pub fn set_teams_from_ron_str(&mut self, str: &str) -> Result<(), RulesError> {
+        let teams = TeamRuleSet::from_ron_str(str)?;
+        self.0.teams = teams;
+        Ok(())
+    }
+
     /// Adds a new initial piece to the layout.
     pub fn add_initial_piece(&mut self, piece: InitialPiece) {
         self.0.initial_layout.add(piece);
@@ -132,11 +256,41 @@ impl UncheckedGameRules {
         Ok(())
     }
 
+    /// Sets what happens to captured pieces.
+    pub fn set_capture_policy(&mut self, policy: CapturePolicy) {
+        self.0.capture_policy = policy;
+    }
+
+    /// Sets the turn schedule.
+    pub fn set_turn_schedule(&mut self, schedule: TurnSchedule) {
+        self.0.turn_schedule = schedule;
+    }
+
+    /// Sets the time control, `None` for untimed games.
+    pub fn set_time_control(&mut self, time_control: Option<TimeControl>) {
+        self.0.time_control = time_control;
+    }
+
+    /// Sets whether pieces can stack on a tile.
+    pub fn set_stacking(&mut self, stacking: StackMode) {
+        self.0.stacking = stacking;
+    }
+
+    /// Sets how tied players are ranked.
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.0.tie_break = tie_break;
+    }
+
     /// Sets the game over condition from a boolean expression.
     pub fn set_game_over_condition(&mut self, cond: BoolExpr) {
         self.0.game_over_condition = cond;
     }
 
+    /// Sets the draw condition from a boolean expression.
+    pub fn set_draw_condition(&mut self, cond: BoolExpr) {
+        self.0.draw_condition = cond;
+    }
+
     /// Parses the game over condition from a ron string.
     pub fn set_game_over_condition_from_ron_str(&mut self, str: &str) -> Result<(), RulesError> {
         let cond = BoolExpr::from_ron_str(str)?;
@@ -152,30 +306,125 @@ impl UncheckedGameRules {
         }
 
         // Check board size.
-        if self.0.board.rows() <= 0 || self.0.board.cols() <= 0 {
+        if !self.0.board.is_valid() {
             return Err(RulesError::InvalidBoardSize);
         }
 
+        if !self.0.board.is_wrap_valid() {
+            return Err(RulesError::InvalidWrap);
+        }
+
         // Check pieces
         if self.0.pieces.is_empty() {
             return Err(RulesError::NoAddedPiece);
         }
 
+        // Check piece names: they are written as bare identifiers
+        for (model, _) in self.0.pieces.iter() {
+            if !is_valid_name(model.name()) {
+                return Err(RulesError::InvalidName(model.to_string()));
+            }
+        }
+
+        // Check promotions: target models must be declared in `pieces`
+        for (model, rules) in self.0.pieces.iter() {
+            let Some(promotion) = rules.promotion() else {
+                continue;
+            };
+
+            if promotion.models().is_empty() {
+                return Err(RulesError::NoPromotionModel(model));
+            }
+
+            for target in promotion.models() {
+                let _ = self.0.pieces.get_by_model(*target)?;
+            }
+        }
+
+        // Check capture policy: converted models must be declared in `pieces`
+        for model in self.0.capture_policy.models() {
+            let _ = self.0.pieces.get_by_model(model)?;
+        }
+
         // Check players
         if self.0.players.is_empty() {
             return Err(RulesError::NoAddedPlayer);
         }
 
+        // Check player names: they are written as bare identifiers
+        for (color, _) in self.0.players.iter() {
+            if !is_valid_name(color.name()) {
+                return Err(RulesError::InvalidName(color.to_string()));
+            }
+        }
+
+        // Check piece colors: overrides and player lists must refer to declared players
+        for (_, rules) in self.0.pieces.iter() {
+            for color in rules.colors() {
+                let _ = self.0.players.get_by_color(color)?;
+            }
+        }
+
+        // Check teams: members must be declared players and belong to one team only
+        {
+            let mut seen = HashSet::new();
+
+            for (_, members) in self.0.teams.iter() {
+                for color in members {
+                    let _ = self.0.players.get_by_color(*color)?;
+
+                    if !seen.insert(*color) {
+                        return Err(RulesError::DuplicateTeamMember(*color));
+                    }
+                }
+            }
+        }
+
+        // Check team names: team states must refer to declared teams or players on their own
+        {
+            let mut names = Vec::new();
+
+            for cond in [&self.0.draw_condition, &self.0.game_over_condition] {
+                cond.visit_bools(&mut |expr| {
+                    if let BoolExpr::TeamStateEqual(name, _) = expr {
+                        names.push(name);
+                    }
+                });
+            }
+
+            for name in names {
+                if !self.0.teams.has_team(name, &self.0.players) {
+                    return Err(RulesError::NoSuchTeam(name.clone()));
+                }
+            }
+        }
+
+        // Check turn schedule
+        if !self.0.turn_schedule.is_valid() {
+            return Err(RulesError::InvalidActionsPerTurn);
+        }
+
+        if !self.0.turn_schedule.is_dice_valid() {
+            return Err(RulesError::InvalidDice);
+        }
+
+        // Check time control
+        if !self
+            .0
+            .time_control
+            .as_ref()
+            .is_none_or(TimeControl::is_valid)
+        {
+            return Err(RulesError::InvalidTimeControl);
+        }
+
         // Check initial layout:
         // - Colors must exist in `players`
         // - Models must exist in `pieces`
-        // - For each (model, color), the number of initial pieces must not exceed the model's count
+        // - For each (model, color), the number of initial pieces must not exceed the model's count for that color
         // - Each position must be inside the board
         // - Positions must not be duplicated
         {
-            let rows = self.0.board.rows();
-            let cols = self.0.board.cols();
-
             // Tally per (model, color)
             let mut per_color_model: HashMap<(PieceModel, PieceColor), usize> = HashMap::new();
             // Track occupied coordinates to catch duplicates
@@ -186,13 +435,13 @@ impl UncheckedGameRules {
                 let color = piece.color();
                 let pos = piece.pos();
 
-                // 1) board bounds check: 0 <= r < rows, 0 <= c < cols
-                if pos.row() < 0 || pos.row() >= rows || pos.col() < 0 || pos.col() >= cols {
+                // 1) board bounds check: the position must be a tile of the board
+                if !self.0.board.contains(pos) {
                     return Err(RulesError::InitialPosOutOfBoard(pos));
                 }
 
-                // 2) duplicate position check
-                if !occupied.insert(pos) {
+                // 2) duplicate position check, later pieces stack on earlier ones if allowed
+                if !occupied.insert(pos) && !self.0.stacking.is_enabled() {
                     return Err(RulesError::DuplicateInitialPos(pos));
                 }
 
@@ -206,7 +455,7 @@ impl UncheckedGameRules {
                 let cnt = per_color_model.entry((model, color)).or_insert(0);
                 *cnt += 1;
 
-                if let Count::Finite(limit) = pr.count() {
+                if let Count::Finite(limit) = pr.count_for(color) {
                     if *cnt > limit {
                         // Initial layout exceeds the allowed count for this (model, color)
                         return Err(RulesError::CountDepleted);
@@ -222,11 +471,24 @@ impl UncheckedGameRules {
 impl Default for UncheckedGameRules {
     fn default() -> Self {
         Self(GameRulesInner {
-            name: String::new(),
+            version: CURRENT_VERSION,
+            include: Vec::new(),
+            name: LocalizedText::from(""),
+            metadata: Metadata::default(),
             board: BoardRuleSet::new(),
             pieces: PieceRuleSet::new(),
             players: PlayerRuleSet::new(),
+            teams: TeamRuleSet::new(),
             initial_layout: InitialLayout::new(),
+            capture_policy: CapturePolicy::default(),
+            turn_schedule: TurnSchedule::default(),
+            time_control: None,
+            stacking: StackMode::default(),
+            draw_condition: BoolExpr::False,
+            tie_break: TieBreak::default(),
+            base_dir: PathBuf::new(),
+            inheritance: None,
+            locale: String::new(),
             game_over_condition: BoolExpr::False,
         })
     }
@@ -240,19 +502,86 @@ impl Default for UncheckedGameRules {
 pub struct CheckedGameRules(GameRulesInner);
 
 impl CheckedGameRules {
-    /// Saves the game rules to a file.
+    /// Saves the game rules to a file, with the format detected from its extension.
     pub fn save<P>(&self, path: P) -> Result<(), RulesError>
     where
         P: AsRef<Path>,
     {
-        to_ron_file(&self.0, path)
+        let format = Format::from_path(&path);
+        self.save_as(path, format)
+    }
+
+    /// Saves the game rules to a JSON file.
+    pub fn save_json<P>(&self, path: P) -> Result<(), RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        self.save_as(path, Format::Json)
+    }
+
+    /// Saves the game rules to a TOML file.
+    pub fn save_toml<P>(&self, path: P) -> Result<(), RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        self.save_as(path, Format::Toml)
     }
 
-    /// Returns the name of the game rules.
+    fn save_as<P>(&self, path: P, format: Format) -> Result<(), RulesError>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(fs::write(path, format.write(&self.0)?)?)
+    }
+
+    /// Returns the selected locale, empty if none.
+    pub fn locale(&self) -> &str {
+        &self.0.locale
+    }
+
+    /// Returns the name of the game rules in the selected locale.
     pub fn name(&self) -> &str {
+        self.0.name.get(&self.0.locale)
+    }
+
+    /// Returns the name of the game rules in every language.
+    pub fn names(&self) -> &LocalizedText {
         &self.0.name
     }
 
+    /// Returns the description, authors and other information shown to players.
+    pub fn metadata(&self) -> &Metadata {
+        &self.0.metadata
+    }
+
+    /// Returns the description in the selected locale, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.0.metadata.description(&self.0.locale)
+    }
+
+    /// Returns the Markdown text explaining how to play in the selected locale, if any.
+    pub fn how_to_play(&self) -> Option<&str> {
+        self.0.metadata.how_to_play(&self.0.locale)
+    }
+
+    /// Returns the base rules file of a variant, relative to the variant.
+    pub fn extends(&self) -> Option<&Path> {
+        self.0
+            .inheritance
+            .as_ref()
+            .map(|inheritance| inheritance.base.as_path())
+    }
+
+    /// Returns if a top-level field, e.g. `"pieces"`, is inherited from the base rules.
+    ///
+    /// Fields of rules that are not a variant are never inherited.
+    pub fn is_inherited(&self, field: &str) -> bool {
+        self.0
+            .inheritance
+            .as_ref()
+            .is_some_and(|inheritance| !inheritance.overridden.contains(field))
+    }
+
     /// Returns the board row count.
     pub const fn board_rows(&self) -> i64 {
         self.0.board.rows()
@@ -263,6 +592,26 @@ impl CheckedGameRules {
         self.0.board.cols()
     }
 
+    /// Returns the board topology.
+    pub const fn board_topology(&self) -> Topology {
+        self.0.board.topology()
+    }
+
+    /// Returns which board axes wrap around.
+    pub const fn board_wrap(&self) -> Wrap {
+        self.0.board.wrap()
+    }
+
+    /// Returns the lengths of the wrapped board axes, for normalising positions.
+    pub fn board_wrap_lengths(&self) -> BoardWrap {
+        self.0.board.wrap_lengths()
+    }
+
+    /// Returns if the position is a tile of the board.
+    pub fn board_contains(&self, pos: Pos) -> bool {
+        self.0.board.contains(pos)
+    }
+
     /// Returns the size of each tile.
     pub const fn tile_size() -> f32 {
         BoardRuleSet::tile_size()
@@ -298,11 +647,110 @@ impl CheckedGameRules {
         self.0.players.iter()
     }
 
+    /// Returns the name of a piece kind shown in the UI, in the selected locale.
+    pub fn model_name(&self, model: PieceModel) -> &str {
+        self.0
+            .pieces
+            .get_by_model(model)
+            .ok()
+            .and_then(|rules| rules.display_name(&self.0.locale))
+            .unwrap_or(model.name())
+    }
+
+    /// Returns the built-in mesh used for a piece kind.
+    ///
+    /// A declared mesh comes first, then the built-in mesh with the same name,
+    /// then one assigned in declaration order.
+    pub fn model_mesh(&self, model: PieceModel) -> BuiltinModel {
+        self.0
+            .pieces
+            .get_by_model(model)
+            .ok()
+            .and_then(PieceRules::mesh)
+            .or_else(|| BuiltinModel::from_name(model.name()))
+            .unwrap_or_else(|| {
+                let index = self.0.pieces.index_of(model).unwrap_or(0);
+                BuiltinModel::ALL[index % BuiltinModel::ALL.len()]
+            })
+    }
+
+    /// Returns the mesh file of a piece kind, resolved against the rules file.
+    pub fn model_mesh_file(&self, model: PieceModel) -> Option<PathBuf> {
+        let file = self.0.pieces.get_by_model(model).ok()?.mesh_file()?;
+        Some(self.0.base_dir.join(file))
+    }
+
+    /// Returns the texture file of a piece kind, resolved against the rules file.
+    pub fn model_texture_file(&self, model: PieceModel) -> Option<PathBuf> {
+        let file = self.0.pieces.get_by_model(model).ok()?.texture_file()?;
+        Some(self.0.base_dir.join(file))
+    }
+
+    /// Returns the label color of a piece kind, if declared.
+    pub fn model_rgb(&self, model: PieceModel) -> Option<Rgb> {
+        self.0
+            .pieces
+            .get_by_model(model)
+            .ok()
+            .and_then(PieceRules::rgb)
+    }
+
+    /// Returns the name of a player shown in the UI, in the selected locale.
+    pub fn color_name(&self, color: PieceColor) -> &str {
+        self.0
+            .players
+            .get_by_color(color)
+            .ok()
+            .and_then(|rules| rules.display_name(&self.0.locale))
+            .unwrap_or(color.name())
+    }
+
+    /// Returns the built-in color used for a player's pieces.
+    ///
+    /// A declared color comes first, then the built-in color with the same name,
+    /// then one assigned in declaration order.
+    pub fn color_visual(&self, color: PieceColor) -> BuiltinColor {
+        self.0
+            .players
+            .get_by_color(color)
+            .ok()
+            .and_then(PlayerRules::color)
+            .or_else(|| BuiltinColor::from_name(color.name()))
+            .unwrap_or_else(|| {
+                let index = self.0.players.index_of(color).unwrap_or(0);
+                BuiltinColor::ALL[index % BuiltinColor::ALL.len()]
+            })
+    }
+
+    /// Returns the exact color of a player's pieces, if declared.
+    pub fn color_rgb(&self, color: PieceColor) -> Option<Rgb> {
+        self.0
+            .players
+            .get_by_color(color)
+            .ok()
+            .and_then(PlayerRules::rgb)
+    }
+
     /// Converts players into a ron string.
     pub fn players_to_ron_str(&self) -> Result<String, RulesError> {
         self.0.players.to_ron_str()
     }
 
+    /// Returns all declared teams with their members.
+    pub fn teams(&self) -> impl Iterator<Item = (&str, &[PieceColor])> {
+        self.0.teams.iter()
+    }
+
+    /// Returns if two colors play on the same team.
+    pub fn same_team(&self, a: PieceColor, b: PieceColor) -> bool {
+        self.0.teams.same_team(a, b)
+    }
+
+    /// Converts teams into a ron string.
+    pub fn teams_to_ron_str(&self) -> Result<String, RulesError> {
+        self.0.teams.to_ron_str()
+    }
+
     /// Returns all initial pieces.
     pub fn initial_pieces(&self) -> impl Iterator<Item = &InitialPiece> {
         self.0.initial_layout.pieces()
@@ -313,6 +761,36 @@ impl CheckedGameRules {
         self.0.initial_layout.to_ron_str()
     }
 
+    /// Returns what happens to captured pieces.
+    pub fn capture_policy(&self) -> &CapturePolicy {
+        &self.0.capture_policy
+    }
+
+    /// Returns the turn schedule.
+    pub fn turn_schedule(&self) -> &TurnSchedule {
+        &self.0.turn_schedule
+    }
+
+    /// Returns the time control, if the game is timed.
+    pub fn time_control(&self) -> Option<&TimeControl> {
+        self.0.time_control.as_ref()
+    }
+
+    /// Returns whether pieces can stack on a tile.
+    pub fn stacking(&self) -> StackMode {
+        self.0.stacking
+    }
+
+    /// Returns how tied players are ranked.
+    pub fn tie_break(&self) -> TieBreak {
+        self.0.tie_break
+    }
+
+    /// Returns if any player has a score.
+    pub fn has_scores(&self) -> bool {
+        self.0.players.iter().any(|(_, rules)| rules.has_score())
+    }
+
     /// Evaluates game over condition.
     pub fn evaluate_game_over_condition<C>(&self, ctx: &C) -> Result<bool, C::Error>
     where
@@ -321,6 +799,19 @@ impl CheckedGameRules {
         self.0.game_over_condition.evaluate(ctx)
     }
 
+    /// Evaluates draw condition.
+    pub fn evaluate_draw_condition<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        self.0.draw_condition.evaluate(ctx)
+    }
+
+    /// Converts draw condition into a ron string.
+    pub fn draw_condition_to_ron_str(&self) -> Result<String, RulesError> {
+        self.0.draw_condition.to_ron_str()
+    }
+
     /// Converts game over condition into a ron string.
     pub fn game_over_condition_to_ron_str(&self) -> Result<String, RulesError> {
         self.0.game_over_condition.to_ron_str()
@@ -336,7 +827,7 @@ impl Default for CheckedGameRules {
         // At least one type of piece, `Cube` as default.
         pieces
             .add(
-                PieceModel::Cube,
+                PieceModel::from(BuiltinModel::Cube),
                 PieceRules::new(Count::Finite(10), BoolExpr::True, BoolExpr::True),
             )
             .unwrap();
@@ -344,45 +835,71 @@ impl Default for CheckedGameRules {
         // At least one player, `White` as default.
         players
             .add(
-                PieceColor::White,
+                PieceColor::from(BuiltinColor::White),
                 PlayerRules::new(BoolExpr::False, BoolExpr::False),
             )
             .unwrap();
 
         // Add some initial pieces.
         initial_layout.add(InitialPiece::new(
-            PieceModel::Cube,
-            PieceColor::White,
+            PieceModel::from(BuiltinModel::Cube),
+            PieceColor::from(BuiltinColor::White),
             Pos::new(0, 0),
         ));
 
         initial_layout.add(InitialPiece::new(
-            PieceModel::Cube,
-            PieceColor::White,
+            PieceModel::from(BuiltinModel::Cube),
+            PieceColor::from(BuiltinColor::White),
             Pos::new(1, 1),
         ));
 
         initial_layout.add(InitialPiece::new(
-            PieceModel::Cube,
-            PieceColor::White,
+            PieceModel::from(BuiltinModel::Cube),
+            PieceColor::from(BuiltinColor::White),
             Pos::new(2, 2),
         ));
 
         Self(GameRulesInner {
+            version: CURRENT_VERSION,
+            include: Vec::new(),
             name: "Default Rules".into(),
+            metadata: Metadata::default(),
             board: BoardRuleSet::new(),
             pieces,
             players,
+            teams: TeamRuleSet::new(),
             initial_layout,
+            capture_policy: CapturePolicy::default(),
+            turn_schedule: TurnSchedule::default(),
+            time_control: None,
+            stacking: StackMode::default(),
+            draw_condition: BoolExpr::False,
+            tie_break: TieBreak::default(),
+            base_dir: PathBuf::new(),
+            inheritance: None,
+            locale: String::new(),
             game_over_condition: BoolExpr::False,
         })
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "GameRules")]
 struct GameRulesInner {
-    /// Name of the game
-    name: String,
+    /// Version of the rules format, 1 if absent
+    #[serde(default = "migration::first_version")]
+    version: u32,
+
+    /// Files whose pieces and players are merged into these rules, relative to this file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include: Vec<PathBuf>,
+
+    /// Name of the game, per locale if needed
+    name: LocalizedText,
+
+    /// Description, authors and how to play
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    metadata: Metadata,
 
     /// Board configuration
     board: BoardRuleSet,
@@ -393,9 +910,55 @@ struct GameRulesInner {
     /// Player configuration
     players: PlayerRuleSet,
 
+    /// Team configuration
+    #[serde(default)]
+    teams: TeamRuleSet,
+
     /// Piece placement layout at the start of the game
     initial_layout: InitialLayout,
 
+    /// What happens to captured pieces
+    #[serde(default)]
+    capture_policy: CapturePolicy,
+
+    /// Turn order and actions per turn
+    #[serde(default)]
+    turn_schedule: TurnSchedule,
+
+    /// Time control of every player, untimed if absent
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+
+    /// Whether pieces can stack on a tile
+    #[serde(default)]
+    stacking: StackMode,
+
+    /// Draw condition, ends the game with all active players drawn
+    #[serde(default = "default_draw_condition")]
+    #[schemars(extend("x-phase" = "game_over"))]
+    draw_condition: BoolExpr,
+
+    /// How players with equal results and scores are ranked
+    #[serde(default)]
+    tie_break: TieBreak,
+
     /// Game termination condition
+    #[schemars(extend("x-phase" = "game_over"))]
     game_over_condition: BoolExpr,
+
+    /// Directory of the rules file, asset files are resolved against it
+    #[serde(skip)]
+    base_dir: PathBuf,
+
+    /// The base rules and the fields declared by a variant
+    #[serde(skip)]
+    inheritance: Option<Inheritance>,
+
+    /// Locale of the texts shown to players
+    #[serde(skip)]
+    locale: String,
+}
+
+fn default_draw_condition() -> BoolExpr {
+    BoolExpr::False
 }
@@ -0,0 +1,127 @@
+use crate::{
+    RulesError,
+    utils::{from_ron_str, to_ron_str},
+};
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, Visitor},
+};
+use std::fmt;
+
+/// A text shown to players, in one or more languages.
+///
+/// Written as a string, or as a map from locale to text, e.g. `{"en": "Chariot", "zh": "车"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    /// The same text in every language.
+    Plain(String),
+
+    /// Texts keyed by locale, e.g. `en`, `zh` or `ja-JP`, the first one is the fallback.
+    PerLocale(IndexMap<String, String>),
+}
+
+impl LocalizedText {
+    /// Returns the text in the given locale.
+    ///
+    /// Falls back to another region of the same language, then to the first text.
+    pub fn get(&self, locale: &str) -> &str {
+        let texts = match self {
+            LocalizedText::Plain(text) => return text,
+            LocalizedText::PerLocale(texts) => texts,
+        };
+
+        texts
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(locale))
+            .or_else(|| {
+                texts
+                    .iter()
+                    .find(|(key, _)| language(key).eq_ignore_ascii_case(language(locale)))
+            })
+            .or_else(|| texts.first())
+            .map_or("", |(_, text)| text)
+    }
+
+    /// Returns the fallback text.
+    pub fn fallback(&self) -> &str {
+        self.get("")
+    }
+
+    /// Returns if there is no text in any language.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            LocalizedText::Plain(text) => text.is_empty(),
+            LocalizedText::PerLocale(texts) => texts.values().all(String::is_empty),
+        }
+    }
+
+    /// Parses from a ron string.
+    pub fn from_ron_str(str: &str) -> Result<Self, RulesError> {
+        from_ron_str(str)
+    }
+
+    /// Converts into a ron string.
+    pub fn to_ron_str(&self) -> Result<String, RulesError> {
+        to_ron_str(self)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(text: String) -> Self {
+        LocalizedText::Plain(text)
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        LocalizedText::Plain(text.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedText {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Visited directly rather than untagged, so errors keep their position.
+        struct LocalizedTextVisitor;
+
+        impl<'de> Visitor<'de> for LocalizedTextVisitor {
+            type Value = LocalizedText;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a map from locale to string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LocalizedText::Plain(v.to_string()))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut texts = IndexMap::new();
+
+                while let Some((locale, text)) = map.next_entry()? {
+                    texts.insert(locale, text);
+                }
+
+                Ok(LocalizedText::PerLocale(texts))
+            }
+        }
+
+        deserializer.deserialize_any(LocalizedTextVisitor)
+    }
+}
+
+/// Returns the language of a locale, e.g. `zh` for `zh-TW`.
+fn language(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
@@ -0,0 +1,97 @@
+use crate::{
+    RulesError,
+    locale::LocalizedText,
+    utils::{from_ron_str, to_ron_str},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about the game rules shown to players, none of it affects the game.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Metadata {
+    /// A short description of the game, per locale if needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<LocalizedText>,
+
+    /// The authors of the rules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+
+    /// The license of the rules, e.g. `MIT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+
+    /// The version of the rules, unrelated to the version of the format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+
+    /// Keywords to find the rules by, e.g. `abstract` or `chess-like`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+
+    /// The numbers of players the game is best played with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recommended_players: Vec<u32>,
+
+    /// How to play the game in Markdown, per locale if needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    how_to_play: Option<LocalizedText>,
+}
+
+impl Metadata {
+    /// Returns the description in the given locale, if any.
+    pub fn description(&self, locale: &str) -> Option<&str> {
+        self.description.as_ref().map(|text| text.get(locale))
+    }
+
+    /// Returns the authors.
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    /// Returns the license, if any.
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    /// Returns the version of the rules, if any.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the tags.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the recommended numbers of players.
+    pub fn recommended_players(&self) -> &[u32] {
+        &self.recommended_players
+    }
+
+    /// Returns the Markdown text explaining how to play in the given locale, if any.
+    pub fn how_to_play(&self, locale: &str) -> Option<&str> {
+        self.how_to_play.as_ref().map(|text| text.get(locale))
+    }
+
+    /// Returns if nothing is declared.
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.authors.is_empty()
+            && self.license.is_none()
+            && self.version.is_none()
+            && self.tags.is_empty()
+            && self.recommended_players.is_empty()
+            && self.how_to_play.is_none()
+    }
+
+    /// Parses from a ron string.
+    pub fn from_ron_str(str: &str) -> Result<Self, RulesError> {
+        from_ron_str(str)
+    }
+
+    /// Converts into a ron string.
+    pub fn to_ron_str(&self) -> Result<String, RulesError> {
+        to_ron_str(self)
+    }
+}
@@ -0,0 +1,253 @@
+//! Upgrades rules documents written in older versions of the format.
+//!
+//! A document without a `version` field is version 1. Each migration upgrades a document by one
+//! version, working on its top-level fields before it is deserialized, so older documents are
+//! upgraded step by step to [`CURRENT_VERSION`].
+//!
+//! Only RON documents are upgraded, JSON and TOML documents must be current.
+
+use crate::{
+    RulesError,
+    format::Format,
+    utils::{intern, to_ron_str},
+};
+use indexmap::IndexMap;
+use ron::value::RawValue;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{DeserializeOwned, MapAccess, Visitor},
+    ser::SerializeStruct,
+};
+use std::{borrow::Cow, fmt};
+
+/// The version of the rules format written by this crate.
+pub const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Upgrades a document by one version.
+type Migration = fn(&mut Document) -> Result<(), RulesError>;
+
+/// Migrations in order, the first one upgrades version 1 to version 2.
+///
+/// Append a migration here whenever a change to the format breaks older documents.
+const MIGRATIONS: &[Migration] = &[];
+
+/// Returns the version of a rules document.
+pub fn version_of(ron: &str) -> Result<u32, RulesError> {
+    Document::parse(ron)?.version()
+}
+
+/// Upgrades a rules document to the current version.
+///
+/// The document is rewritten even if it is already current.
+pub fn upgrade(ron: &str) -> Result<String, RulesError> {
+    upgrade_with(ron, MIGRATIONS)
+}
+
+/// Upgrades a rules document through the given migrations, the first one upgrading version 1.
+fn upgrade_with(ron: &str, migrations: &[Migration]) -> Result<String, RulesError> {
+    let latest = migrations.len() as u32 + 1;
+    let mut document = Document::parse(ron)?;
+    let version = document.version()?;
+
+    if version > latest {
+        return Err(RulesError::UnsupportedVersion(version));
+    }
+
+    for migration in &migrations[version as usize - 1..] {
+        migration(&mut document)?;
+    }
+
+    document.set_version(latest)?;
+    to_ron_str(&document)
+}
+
+/// Upgrades a rules document only if it is older than the current version.
+///
+/// Current documents are returned untouched, so errors keep pointing at the original text.
+fn migrate(ron: &str) -> Result<Cow<'_, str>, RulesError> {
+    match version_of(ron)? {
+        CURRENT_VERSION => Ok(Cow::Borrowed(ron)),
+        _ => upgrade(ron).map(Cow::Owned),
+    }
+}
+
+/// Parses a document in the specified format, upgrading RON written in older versions first.
+///
+/// JSON and TOML documents are not upgraded, older ones are rejected as unsupported.
+pub(crate) fn parse_current<T>(str: &str, format: Format) -> Result<T, RulesError>
+where
+    T: DeserializeOwned,
+{
+    if format == Format::Ron {
+        return format.parse(&migrate(str)?);
+    }
+
+    let Versioned { version } = format.parse(str)?;
+
+    if version != CURRENT_VERSION {
+        return Err(RulesError::UnsupportedVersion(version));
+    }
+
+    format.parse(str)
+}
+
+/// Only the version of a rules document, other fields are ignored.
+#[derive(Debug, Deserialize)]
+struct Versioned {
+    #[serde(default = "first_version")]
+    version: u32,
+}
+
+/// The version of documents without a `version` field.
+pub(crate) fn first_version() -> u32 {
+    1
+}
+
+/// The top-level fields of a rules document, with their values kept as raw RON.
+#[derive(Debug)]
+struct Document(IndexMap<&'static str, Box<RawValue>>);
+
+impl Document {
+    /// Parses a document.
+    fn parse(ron: &str) -> Result<Self, RulesError> {
+        Ok(ron::from_str(ron)?)
+    }
+
+    /// Returns the version of the document, version 1 if absent.
+    fn version(&self) -> Result<u32, RulesError> {
+        let version = match self.0.get("version") {
+            Some(raw) => raw.into_rust()?,
+            None => first_version(),
+        };
+
+        if version == 0 {
+            return Err(RulesError::UnsupportedVersion(version));
+        }
+
+        Ok(version)
+    }
+
+    /// Sets the version of the document, as its first field.
+    fn set_version(&mut self, version: u32) -> Result<(), RulesError> {
+        self.0
+            .insert_before(0, "version", RawValue::from_rust(&version)?);
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Document {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DocumentVisitor;
+
+        impl<'de> Visitor<'de> for DocumentVisitor {
+            type Value = Document;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a rules struct")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut fields = IndexMap::new();
+
+                while let Some((key, value)) = map.next_entry::<String, Box<RawValue>>()? {
+                    fields.insert(intern(&key), value.trim_boxed());
+                }
+
+                Ok(Document(fields))
+            }
+        }
+
+        deserializer.deserialize_struct("GameRules", &[], DocumentVisitor)
+    }
+}
+
+impl Serialize for Document {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GameRules", self.0.len())?;
+
+        for (key, value) in &self.0 {
+            state.serialize_field(key, value)?;
+        }
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A format change for the tests: version 2 renames `title` to `name`.
+    fn rename_title(document: &mut Document) -> Result<(), RulesError> {
+        if let Some(title) = document.0.shift_remove("title") {
+            document.0.insert("name", title);
+        }
+
+        Ok(())
+    }
+
+    /// A format change for the tests: version 3 keys the name by locale.
+    fn localize_name(document: &mut Document) -> Result<(), RulesError> {
+        if let Some(name) = document.0.get_mut("name") {
+            let localized = format!("{{\"en\": {}}}", name.get_ron());
+            *name = RawValue::from_boxed_ron(localized.into())?;
+        }
+
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[rename_title, localize_name];
+
+    /// Returns the version and the name of an upgraded document.
+    fn upgraded(ron: &str) -> (u32, IndexMap<String, String>) {
+        let document = Document::parse(&upgrade_with(ron, TEST_MIGRATIONS).unwrap()).unwrap();
+
+        (
+            document.version().unwrap(),
+            document.0["name"].into_rust().unwrap(),
+        )
+    }
+
+    #[test]
+    fn documents_are_upgraded_step_by_step() {
+        let (version, name) = upgraded(r#"(title: "Old", board: (rows: 8, cols: 8))"#);
+
+        assert_eq!(version, 3);
+        assert_eq!(
+            name,
+            IndexMap::from([("en".to_string(), "Old".to_string())])
+        );
+    }
+
+    #[test]
+    fn upgrades_start_at_the_document_version() {
+        // A version 2 document has a name already, a `title` would be an unknown field.
+        let ron = r#"(version: 2, name: "Newer", title: "Ignored")"#;
+        let document = Document::parse(&upgrade_with(ron, TEST_MIGRATIONS).unwrap()).unwrap();
+
+        assert!(document.0.contains_key("title"));
+        assert_eq!(upgraded(ron).1["en"], "Newer");
+        assert_eq!(
+            upgraded("(version: 3, name: {\"zh\": \"新\"})").1["zh"],
+            "新"
+        );
+    }
+
+    #[test]
+    fn versions_after_the_last_migration_are_rejected() {
+        assert!(matches!(
+            upgrade_with("(version: 4)", TEST_MIGRATIONS),
+            Err(RulesError::UnsupportedVersion(4))
+        ));
+    }
+}
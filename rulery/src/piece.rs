@@ -1,15 +1,27 @@
 use crate::{
     RulesError,
+    capture::CaptureMode,
     count::Count,
     expr::{Context, boolean::BoolExpr},
-    utils::{from_ron_str, to_ron_str},
+    locale::LocalizedText,
+    promotion::PromotionRules,
+    utils::{deserialize_name, deserialize_some, from_ron_str, intern, to_ron_str},
 };
 use indexmap::{IndexMap, map::Entry};
-use serde::{Deserialize, Serialize};
-use std::fmt;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    fmt,
+    path::{Path, PathBuf},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub enum PieceModel {
+/// Built-in piece meshes.
+///
+/// Their names double as piece kinds, so rule files written before kinds could be
+/// declared still load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum BuiltinModel {
     Cube,
     Sphere,
     Cylinder,
@@ -19,23 +31,49 @@ pub enum PieceModel {
     Tetrahedron,
 }
 
-impl fmt::Display for PieceModel {
+impl BuiltinModel {
+    /// All built-in meshes, in declaration order.
+    pub const ALL: [BuiltinModel; 7] = [
+        BuiltinModel::Cube,
+        BuiltinModel::Sphere,
+        BuiltinModel::Cylinder,
+        BuiltinModel::Capsule,
+        BuiltinModel::Cone,
+        BuiltinModel::Torus,
+        BuiltinModel::Tetrahedron,
+    ];
+
+    /// Returns the built-in mesh with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|model| model.name() == name)
+    }
+
+    /// Returns the name of the built-in mesh.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinModel::Cube => "Cube",
+            BuiltinModel::Sphere => "Sphere",
+            BuiltinModel::Cylinder => "Cylinder",
+            BuiltinModel::Capsule => "Capsule",
+            BuiltinModel::Cone => "Cone",
+            BuiltinModel::Torus => "Torus",
+            BuiltinModel::Tetrahedron => "Tetrahedron",
+        }
+    }
+}
+
+impl fmt::Display for BuiltinModel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let name = match self {
-            PieceModel::Cube => "Cube",
-            PieceModel::Sphere => "Sphere",
-            PieceModel::Cylinder => "Cylinder",
-            PieceModel::Capsule => "Capsule",
-            PieceModel::Cone => "Cone",
-            PieceModel::Torus => "Torus",
-            PieceModel::Tetrahedron => "Tetrahedron",
-        };
-        write!(f, "{name}")
+        write!(f, "{}", self.name())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub enum PieceColor {
+/// Built-in piece colors.
+///
+/// Their names double as players, so rule files written before players could be
+/// declared still load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum BuiltinColor {
     White,
     Black,
     Red,
@@ -45,39 +83,288 @@ pub enum PieceColor {
     Purple,
 }
 
+impl BuiltinColor {
+    /// All built-in colors, in declaration order.
+    pub const ALL: [BuiltinColor; 7] = [
+        BuiltinColor::White,
+        BuiltinColor::Black,
+        BuiltinColor::Red,
+        BuiltinColor::Yellow,
+        BuiltinColor::Green,
+        BuiltinColor::Cyan,
+        BuiltinColor::Purple,
+    ];
+
+    /// Returns the built-in color with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+
+    /// Returns the name of the built-in color.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinColor::White => "White",
+            BuiltinColor::Black => "Black",
+            BuiltinColor::Red => "Red",
+            BuiltinColor::Yellow => "Yellow",
+            BuiltinColor::Green => "Green",
+            BuiltinColor::Cyan => "Cyan",
+            BuiltinColor::Purple => "Purple",
+        }
+    }
+}
+
+impl fmt::Display for BuiltinColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An RGB color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// A piece kind, declared by name in the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PieceModel(&'static str);
+
+impl PieceModel {
+    /// Creates a piece kind with the given name.
+    pub fn new(name: &str) -> Self {
+        Self(intern(name))
+    }
+
+    /// Returns the name of the piece kind.
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<BuiltinModel> for PieceModel {
+    fn from(model: BuiltinModel) -> Self {
+        Self::new(model.name())
+    }
+}
+
+impl fmt::Display for PieceModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for PieceModel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Written as a bare identifier, like the former enum variants.
+        serializer.serialize_unit_variant("PieceModel", 0, self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for PieceModel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_name(deserializer).map(Self)
+    }
+}
+
+impl JsonSchema for PieceModel {
+    fn schema_name() -> Cow<'static, str> {
+        "PieceModel".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A piece kind declared in `pieces`, or a built-in mesh name.",
+            "type": "string",
+            "pattern": "^[A-Za-z_][A-Za-z0-9_]*$",
+        })
+    }
+}
+
+/// A player, declared by name in the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PieceColor(&'static str);
+
+impl PieceColor {
+    /// Creates a player with the given name.
+    pub fn new(name: &str) -> Self {
+        Self(intern(name))
+    }
+
+    /// Returns the name of the player.
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<BuiltinColor> for PieceColor {
+    fn from(color: BuiltinColor) -> Self {
+        Self::new(color.name())
+    }
+}
+
 impl fmt::Display for PieceColor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let text = match self {
-            PieceColor::White => "White",
-            PieceColor::Black => "Black",
-            PieceColor::Red => "Red",
-            PieceColor::Yellow => "Yellow",
-            PieceColor::Green => "Green",
-            PieceColor::Cyan => "Cyan",
-            PieceColor::Purple => "Purple",
-        };
-        write!(f, "{text}")
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for PieceColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Written as a bare identifier, like the former enum variants.
+        serializer.serialize_unit_variant("PieceColor", 0, self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for PieceColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_name(deserializer).map(Self)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl JsonSchema for PieceColor {
+    fn schema_name() -> Cow<'static, str> {
+        "PieceColor".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A player declared in `players`, or a built-in color name.",
+            "type": "string",
+            "pattern": "^[A-Za-z_][A-Za-z0-9_]*$",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PieceRules {
     /// The maximum number of pieces allowed for this kind.
     count: Count,
 
+    /// Per-color overrides of `count`, for asymmetric games.
+    #[serde(default)]
+    count_per_color: IndexMap<PieceColor, Count>,
+
+    /// The players allowed to use this kind.
+    ///
+    /// If absent, every player can use it.
+    #[serde(default)]
+    players: Option<Vec<PieceColor>>,
+
     /// A boolean expression that defines whether a move is allowed.
+    #[schemars(extend("x-phase" = "movement"))]
     movement: BoolExpr,
 
     /// A boolean expression that defines whether placement is allowed.
+    #[schemars(extend("x-phase" = "placement"))]
     placement: BoolExpr,
+
+    /// A boolean expression that defines whether a piece can be captured by this kind.
+    ///
+    /// Evaluated once per candidate piece, which is exposed as `CaptureRow`/`CaptureCol`.
+    /// If absent, any piece can be captured.
+    #[serde(default)]
+    #[schemars(extend("x-phase" = "capture"))]
+    capture: Option<BoolExpr>,
+
+    /// How this kind captures other pieces.
+    #[serde(default)]
+    capture_mode: CaptureMode,
+
+    /// A boolean expression that defines whether this kind goes on top of an occupied tile
+    /// it moves to, instead of a replace capture, when stacking is enabled.
+    ///
+    /// Evaluated with the top piece of the tile as the capture candidate.
+    /// If absent, the piece always stacks.
+    #[serde(default)]
+    #[schemars(extend("x-phase" = "capture"))]
+    stack: Option<BoolExpr>,
+
+    /// Promotion rules, checked after each move.
+    #[serde(default)]
+    promotion: Option<PromotionRules>,
+
+    /// A boolean expression that defines whether a piece of this kind is shown to a player.
+    ///
+    /// Evaluated once per piece and viewer, exposed as `ViewedRow`/`ViewedCol` and `ViewerColor`.
+    /// If absent, the piece is always shown.
+    #[serde(default)]
+    #[schemars(extend("x-phase" = "visibility"))]
+    visibility: Option<BoolExpr>,
+
+    /// A boolean expression that defines whether the model of a shown piece is revealed.
+    ///
+    /// Unrevealed pieces keep their color but use a generic mesh (Stratego).
+    /// If absent, the model is always revealed.
+    #[serde(default)]
+    #[schemars(extend("x-phase" = "visibility"))]
+    model_visibility: Option<BoolExpr>,
+
+    /// The name shown in the UI, per locale if needed, defaults to the kind's identifier.
+    #[serde(default)]
+    display_name: Option<LocalizedText>,
+
+    /// The built-in mesh used for this kind.
+    ///
+    /// If absent, a kind named after a built-in mesh uses it, others are assigned one
+    /// in declaration order.
+    #[serde(default)]
+    mesh: Option<BuiltinModel>,
+
+    /// The color of the kind's label in the UI.
+    #[serde(default)]
+    rgb: Option<Rgb>,
+
+    /// A glTF or OBJ mesh file, relative to the rules file.
+    ///
+    /// Replaces the built-in mesh if it can be found.
+    #[serde(default)]
+    mesh_file: Option<PathBuf>,
+
+    /// A PNG texture file, relative to the rules file.
+    #[serde(default)]
+    texture_file: Option<PathBuf>,
 }
 
 impl PieceRules {
     pub fn new(count: Count, movement: BoolExpr, placement: BoolExpr) -> Self {
         Self {
             count,
+            count_per_color: IndexMap::new(),
+            players: None,
             movement,
             placement,
+            capture: None,
+            capture_mode: CaptureMode::default(),
+            stack: None,
+            promotion: None,
+            visibility: None,
+            model_visibility: None,
+            display_name: None,
+            mesh: None,
+            rgb: None,
+            mesh_file: None,
+            texture_file: None,
+        }
+    }
+
+    /// Resolves the asset files against `dir`, for pieces declared in another file.
+    pub(crate) fn rebase_files(&mut self, dir: &Path) {
+        for file in [&mut self.mesh_file, &mut self.texture_file]
+            .into_iter()
+            .flatten()
+        {
+            *file = dir.join(&*file);
         }
     }
 
@@ -86,6 +373,35 @@ impl PieceRules {
         self.count
     }
 
+    /// Returns the count of pieces allowed for the given color.
+    ///
+    /// Players that can't use this kind get none.
+    pub fn count_for(&self, color: PieceColor) -> Count {
+        if !self.is_available_to(color) {
+            return Count::Finite(0);
+        }
+
+        self.count_per_color
+            .get(&color)
+            .copied()
+            .unwrap_or(self.count)
+    }
+
+    /// Returns if the given color can use this kind.
+    pub fn is_available_to(&self, color: PieceColor) -> bool {
+        self.players
+            .as_ref()
+            .is_none_or(|players| players.contains(&color))
+    }
+
+    /// Returns all colors referenced by the overrides and the player list.
+    pub(crate) fn colors(&self) -> impl Iterator<Item = PieceColor> {
+        self.count_per_color
+            .keys()
+            .chain(self.players.iter().flatten())
+            .copied()
+    }
+
     /// Evaluates the movement condition.
     pub fn can_move<C>(&self, ctx: &C) -> Result<bool, C::Error>
     where
@@ -94,6 +410,85 @@ impl PieceRules {
         self.movement.evaluate(ctx)
     }
 
+    /// Returns how this kind captures other pieces.
+    pub fn capture_mode(&self) -> CaptureMode {
+        self.capture_mode
+    }
+
+    /// Evaluates the capture condition.
+    pub fn can_capture<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        match &self.capture {
+            Some(capture) => capture.evaluate(ctx),
+            None => Ok(true),
+        }
+    }
+
+    /// Evaluates the stack condition.
+    pub fn can_stack<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        match &self.stack {
+            Some(stack) => stack.evaluate(ctx),
+            None => Ok(true),
+        }
+    }
+
+    /// Returns the promotion rules, if any.
+    pub fn promotion(&self) -> Option<&PromotionRules> {
+        self.promotion.as_ref()
+    }
+
+    /// Evaluates whether the piece is shown to the viewer.
+    pub fn is_visible<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        match &self.visibility {
+            Some(visibility) => visibility.evaluate(ctx),
+            None => Ok(true),
+        }
+    }
+
+    /// Evaluates whether the model of the piece is revealed to the viewer.
+    pub fn is_model_visible<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        match &self.model_visibility {
+            Some(visibility) => visibility.evaluate(ctx),
+            None => Ok(true),
+        }
+    }
+
+    /// Returns the declared display name in the given locale, if any.
+    pub fn display_name(&self, locale: &str) -> Option<&str> {
+        self.display_name.as_ref().map(|name| name.get(locale))
+    }
+
+    /// Returns the declared built-in mesh, if any.
+    pub fn mesh(&self) -> Option<BuiltinModel> {
+        self.mesh
+    }
+
+    /// Returns the declared label color, if any.
+    pub fn rgb(&self) -> Option<Rgb> {
+        self.rgb
+    }
+
+    /// Returns the declared mesh file, if any.
+    pub fn mesh_file(&self) -> Option<&Path> {
+        self.mesh_file.as_deref()
+    }
+
+    /// Returns the declared texture file, if any.
+    pub fn texture_file(&self) -> Option<&Path> {
+        self.texture_file.as_deref()
+    }
+
     /// Evaluates the placement condition.
     pub fn can_place<C>(&self, ctx: &C) -> Result<bool, C::Error>
     where
@@ -103,9 +498,145 @@ impl PieceRules {
     }
 }
 
+/// Changes a rules variant makes to an inherited piece kind, absent fields are inherited.
+///
+/// Fields are written as in [`PieceRules`]. A kind the base rules don't declare must set `count`, `movement` and `placement`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PieceOverrides {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    count: Option<Count>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    count_per_color: Option<IndexMap<PieceColor, Count>>,
+
+    #[serde(default)]
+    players: Option<Vec<PieceColor>>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    movement: Option<BoolExpr>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    placement: Option<BoolExpr>,
+
+    #[serde(default)]
+    capture: Option<BoolExpr>,
+
+    #[serde(default, deserialize_with = "deserialize_some")]
+    capture_mode: Option<CaptureMode>,
+
+    #[serde(default)]
+    stack: Option<BoolExpr>,
+
+    #[serde(default)]
+    promotion: Option<PromotionRules>,
+
+    #[serde(default)]
+    visibility: Option<BoolExpr>,
+
+    #[serde(default)]
+    model_visibility: Option<BoolExpr>,
+
+    #[serde(default)]
+    display_name: Option<LocalizedText>,
+
+    #[serde(default)]
+    mesh: Option<BuiltinModel>,
+
+    #[serde(default)]
+    rgb: Option<Rgb>,
+
+    #[serde(default)]
+    mesh_file: Option<PathBuf>,
+
+    #[serde(default)]
+    texture_file: Option<PathBuf>,
+}
+
+impl PieceOverrides {
+    /// Creates the rules of a kind the base rules don't declare.
+    pub(crate) fn into_rules(mut self) -> Option<PieceRules> {
+        let mut rules = PieceRules::new(
+            self.count.take()?,
+            self.movement.take()?,
+            self.placement.take()?,
+        );
+
+        self.apply(&mut rules);
+        Some(rules)
+    }
+
+    /// Replaces the declared fields of inherited rules.
+    pub(crate) fn apply(self, rules: &mut PieceRules) {
+        if let Some(count) = self.count {
+            rules.count = count;
+        }
+
+        if let Some(count_per_color) = self.count_per_color {
+            rules.count_per_color = count_per_color;
+        }
+
+        if let Some(players) = self.players {
+            rules.players = Some(players);
+        }
+
+        if let Some(movement) = self.movement {
+            rules.movement = movement;
+        }
+
+        if let Some(placement) = self.placement {
+            rules.placement = placement;
+        }
+
+        if let Some(capture) = self.capture {
+            rules.capture = Some(capture);
+        }
+
+        if let Some(capture_mode) = self.capture_mode {
+            rules.capture_mode = capture_mode;
+        }
+
+        if let Some(stack) = self.stack {
+            rules.stack = Some(stack);
+        }
+
+        if let Some(promotion) = self.promotion {
+            rules.promotion = Some(promotion);
+        }
+
+        if let Some(visibility) = self.visibility {
+            rules.visibility = Some(visibility);
+        }
+
+        if let Some(model_visibility) = self.model_visibility {
+            rules.model_visibility = Some(model_visibility);
+        }
+
+        if let Some(display_name) = self.display_name {
+            rules.display_name = Some(display_name);
+        }
+
+        if let Some(mesh) = self.mesh {
+            rules.mesh = Some(mesh);
+        }
+
+        if let Some(rgb) = self.rgb {
+            rules.rgb = Some(rgb);
+        }
+
+        if let Some(mesh_file) = self.mesh_file {
+            rules.mesh_file = Some(mesh_file);
+        }
+
+        if let Some(texture_file) = self.texture_file {
+            rules.texture_file = Some(texture_file);
+        }
+    }
+}
+
 /// Uses [`IndexMap`] to ensure a stable iteration order.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
+#[schemars(description = "Piece kinds, keyed by name.")]
 pub(crate) struct PieceRuleSet(IndexMap<PieceModel, PieceRules>);
 
 impl PieceRuleSet {
@@ -120,7 +651,51 @@ impl PieceRuleSet {
                 v.insert(rules);
                 Ok(())
             }
-            Entry::Occupied(_) => Err(RulesError::DuplicateModel),
+            Entry::Occupied(_) => Err(RulesError::DuplicateModel(model)),
+        }
+    }
+
+    /// Adds all piece models of another set, replacing the rules of declared ones.
+    pub(crate) fn replace(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    /// Adds all piece models of another set, after the existing ones.
+    pub(crate) fn merge(&mut self, other: Self) -> Result<(), RulesError> {
+        for (model, rules) in other.0 {
+            self.add(model, rules)?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the changes of the variant at `path` to a piece model, adding it if it is not declared yet.
+    pub(crate) fn apply(
+        &mut self,
+        model: PieceModel,
+        overrides: PieceOverrides,
+        path: &Path,
+    ) -> Result<(), RulesError> {
+        match self.0.entry(model) {
+            Entry::Occupied(mut o) => {
+                overrides.apply(o.get_mut());
+                Ok(())
+            }
+            Entry::Vacant(v) => {
+                v.insert(
+                    overrides
+                        .into_rules()
+                        .ok_or_else(|| RulesError::IncompletePiece(path.to_path_buf(), model))?,
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Resolves the asset files of every piece against `dir`.
+    pub(crate) fn rebase_files(&mut self, dir: &Path) {
+        for rules in self.0.values_mut() {
+            rules.rebase_files(dir);
         }
     }
 
@@ -129,6 +704,11 @@ impl PieceRuleSet {
         self.0.get(&model).ok_or(RulesError::NoSuchModel(model))
     }
 
+    /// Returns the declaration index of the specified model.
+    pub(crate) fn index_of(&self, model: PieceModel) -> Option<usize> {
+        self.0.get_index_of(&model)
+    }
+
     /// Returns all rules.
     pub(crate) fn iter(&self) -> impl Iterator<Item = (PieceModel, &PieceRules)> {
         self.0.iter().map(|(model, rules)| (*model, rules))
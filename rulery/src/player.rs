@@ -1,18 +1,21 @@
 use crate::{
     RulesError,
-    expr::{Context, boolean::BoolExpr},
-    piece::PieceColor,
+    expr::{Context, boolean::BoolExpr, integer::IntExpr},
+    locale::LocalizedText,
+    piece::{BuiltinColor, PieceColor, Rgb},
     utils::{from_ron_str, to_ron_str},
 };
 use indexmap::{IndexMap, map::Entry};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum PlayerState {
     Active,
     Won,
     Lost,
+    Draw,
 }
 
 impl fmt::Display for PlayerState {
@@ -21,24 +24,63 @@ impl fmt::Display for PlayerState {
             PlayerState::Active => write!(f, "Active"),
             PlayerState::Won => write!(f, "Won"),
             PlayerState::Lost => write!(f, "Lost"),
+            PlayerState::Draw => write!(f, "Draw"),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// How players with equal results and scores are ranked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TieBreak {
+    /// Tied players share a rank.
+    #[default]
+    Shared,
+
+    /// The player declared first ranks higher.
+    PlayerOrder,
+
+    /// The player declared last ranks higher, e.g. to compensate for moving second.
+    ReversePlayerOrder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PlayerRules {
     /// A boolean expression that defines whether the player loses.
     ///
     /// In evaluation order, this is usually checked **before** `win_condition`.
     /// If the `lose_condition` is satisfied, the player is considered to have lost
     /// regardless of whether the `win_condition` also holds.
+    #[schemars(extend("x-phase" = "win_or_lose"))]
     lose_condition: BoolExpr,
 
     /// A boolean expression that defines whether the player wins.
     ///
     /// Typically, evaluated only if `lose_condition` is not satisfied.
     /// This ensures that an invalid or failing state cannot be counted as a win.
+    #[schemars(extend("x-phase" = "win_or_lose"))]
     win_condition: BoolExpr,
+
+    /// An integer expression that defines the player's score.
+    ///
+    /// Evaluated at the end of every turn, players are ranked by it when the game ends.
+    #[serde(default)]
+    #[schemars(extend("x-phase" = "win_or_lose"))]
+    score: Option<IntExpr>,
+
+    /// The name shown in the UI, per locale if needed, defaults to the player's identifier.
+    #[serde(default)]
+    display_name: Option<LocalizedText>,
+
+    /// The built-in color used for the player's pieces.
+    ///
+    /// If absent, a player named after a built-in color uses it, others are assigned one
+    /// in declaration order.
+    #[serde(default)]
+    color: Option<BuiltinColor>,
+
+    /// The exact color of the player's pieces, overriding the built-in color.
+    #[serde(default)]
+    rgb: Option<Rgb>,
 }
 
 impl PlayerRules {
@@ -47,9 +89,28 @@ impl PlayerRules {
         Self {
             win_condition,
             lose_condition,
+            score: None,
+            display_name: None,
+            color: None,
+            rgb: None,
         }
     }
 
+    /// Returns the declared display name in the given locale, if any.
+    pub fn display_name(&self, locale: &str) -> Option<&str> {
+        self.display_name.as_ref().map(|name| name.get(locale))
+    }
+
+    /// Returns the declared built-in color, if any.
+    pub fn color(&self) -> Option<BuiltinColor> {
+        self.color
+    }
+
+    /// Returns the declared exact color, if any.
+    pub fn rgb(&self) -> Option<Rgb> {
+        self.rgb
+    }
+
     /// Evaluates player state.
     pub fn evaluate_state<C>(&self, ctx: &C) -> Result<PlayerState, C::Error>
     where
@@ -68,11 +129,28 @@ impl PlayerRules {
         // If neither condition is met, the player is still active.
         Ok(PlayerState::Active)
     }
+
+    /// Returns if the player has a score.
+    pub fn has_score(&self) -> bool {
+        self.score.is_some()
+    }
+
+    /// Evaluates the player's score, if any.
+    pub fn evaluate_score<C>(&self, ctx: &C) -> Result<Option<i64>, C::Error>
+    where
+        C: Context,
+    {
+        self.score
+            .as_ref()
+            .map(|score| score.evaluate(ctx))
+            .transpose()
+    }
 }
 
 /// Uses [`IndexMap`] to ensure a stable iteration order.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
+#[schemars(description = "Players, keyed by name.")]
 pub(crate) struct PlayerRuleSet(IndexMap<PieceColor, PlayerRules>);
 
 impl PlayerRuleSet {
@@ -87,15 +165,41 @@ impl PlayerRuleSet {
                 v.insert(rules);
                 Ok(())
             }
-            Entry::Occupied(_) => Err(RulesError::DuplicateColor),
+            Entry::Occupied(_) => Err(RulesError::DuplicateColor(color)),
+        }
+    }
+
+    /// Adds all players of another set, after the existing ones.
+    pub(crate) fn merge(&mut self, other: Self) -> Result<(), RulesError> {
+        for (color, rules) in other.0 {
+            self.add(color, rules)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds all players of another set, replacing the rules of declared ones.
+    pub(crate) fn replace(&mut self, other: Self) {
+        for (color, rules) in other.0 {
+            self.set(color, rules);
         }
     }
 
+    /// Sets the rules of a player, replacing those of a declared one.
+    pub(crate) fn set(&mut self, color: PieceColor, rules: PlayerRules) {
+        self.0.insert(color, rules);
+    }
+
     /// Returns the player rules with the specified color.
     pub(crate) fn get_by_color(&self, color: PieceColor) -> Result<&PlayerRules, RulesError> {
         self.0.get(&color).ok_or(RulesError::NoSuchColor(color))
     }
 
+    /// Returns the declaration index of the specified color.
+    pub(crate) fn index_of(&self, color: PieceColor) -> Option<usize> {
+        self.0.get_index_of(&color)
+    }
+
     /// Returns an iterator over all player rules.
     pub(crate) fn iter(&self) -> impl Iterator<Item = (PieceColor, &PlayerRules)> {
         self.0.iter().map(|(color, rules)| (*color, rules))
@@ -19,6 +19,14 @@ impl Pos {
     pub const fn col(&self) -> i64 {
         self.1
     }
+
+    /// Returns the number of steps between two hex tiles in axial coordinates.
+    pub const fn hex_distance(&self, other: Pos) -> i64 {
+        let dr = other.0 - self.0;
+        let dc = other.1 - self.1;
+
+        (dr.abs() + dc.abs() + (dr + dc).abs()) / 2
+    }
 }
 
 impl From<(i64, i64)> for Pos {
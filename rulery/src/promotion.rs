@@ -0,0 +1,40 @@
+use crate::{
+    expr::{Context, boolean::BoolExpr},
+    piece::PieceModel,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Promotion rules of a piece kind.
+///
+/// Promotion does not touch the stock: a promoted piece keeps counting against
+/// the model it was placed as, and the stock of the new model is not consumed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PromotionRules {
+    /// A boolean expression that defines whether the piece promotes after a move.
+    #[schemars(extend("x-phase" = "movement"))]
+    condition: BoolExpr,
+
+    /// The models the piece can promote to.
+    models: Vec<PieceModel>,
+}
+
+impl PromotionRules {
+    /// Creates a new promotion rules.
+    pub fn new(condition: BoolExpr, models: Vec<PieceModel>) -> Self {
+        Self { condition, models }
+    }
+
+    /// Returns the models the piece can promote to.
+    pub fn models(&self) -> &[PieceModel] {
+        &self.models
+    }
+
+    /// Evaluates the promotion condition.
+    pub fn can_promote<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        self.condition.evaluate(ctx)
+    }
+}
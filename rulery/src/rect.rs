@@ -1,4 +1,4 @@
-use crate::pos::Pos;
+use crate::{board::BoardWrap, pos::Pos};
 use std::cmp::{max, min};
 
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +26,19 @@ impl Rect {
             && pos.col() <= self.col_max
     }
 
+    /// Returns if the position is inside the rectangle, repeated every board length along wrapped axes.
+    pub fn contains_wrapped(&self, pos: Pos, wrap: BoardWrap) -> bool {
+        fn within(min: i64, max: i64, value: i64, len: Option<i64>) -> bool {
+            match len {
+                Some(len) => max - min + 1 >= len || (value - min).rem_euclid(len) <= max - min,
+                None => value >= min && value <= max,
+            }
+        }
+
+        within(self.row_min, self.row_max, pos.row(), wrap.rows())
+            && within(self.col_min, self.col_max, pos.col(), wrap.cols())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = Pos> {
         (self.row_min..=self.row_max)
             .flat_map(move |row| (self.col_min..=self.col_max).map(move |col| Pos::new(row, col)))
@@ -0,0 +1,30 @@
+//! A machine-readable description of the rules format.
+//!
+//! Expression variants that are only valid in some phases list them in `x-phases`,
+//! and fields holding expressions name the phase they are evaluated in with `x-phase`.
+//! Variants without `x-phases` are valid in every phase.
+
+use crate::{GameRulesInner, RulesError};
+use schemars::generate::SchemaSettings;
+
+/// Phases in which expressions are evaluated.
+pub const PHASES: [&str; 7] = [
+    "movement",
+    "placement",
+    "capture",
+    "visibility",
+    "turn_order",
+    "win_or_lose",
+    "game_over",
+];
+
+/// Returns the JSON Schema of rules files, as pretty-printed JSON.
+///
+/// The schema describes the JSON form of the rules, the RON and TOML forms share its structure.
+pub fn json_schema() -> Result<String, RulesError> {
+    let schema = SchemaSettings::draft2020_12()
+        .into_generator()
+        .into_root_schema_for::<GameRulesInner>();
+
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
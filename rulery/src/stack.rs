@@ -0,0 +1,26 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Whether pieces can stack on a tile, and what moves with the top piece.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum StackMode {
+    /// At most one piece per tile.
+    #[default]
+    Disabled,
+
+    /// Placing or moving onto an occupied tile puts the piece on top of the stack,
+    /// instead of a replace capture. Only the top piece moves.
+    ///
+    /// A piece's `stack` condition can make a move capture the top piece instead.
+    TopPiece,
+
+    /// As `TopPiece`, but the whole stack moves with its top piece.
+    WholeStack,
+}
+
+impl StackMode {
+    /// Returns if pieces can stack on a tile.
+    pub fn is_enabled(&self) -> bool {
+        *self != StackMode::Disabled
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    RulesError,
+    piece::PieceColor,
+    player::PlayerRuleSet,
+    utils::{from_ron_str, to_ron_str},
+};
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Team declarations, keyed by team name.
+///
+/// Players that are not declared in any team play on their own.
+/// Uses [`IndexMap`] to ensure a stable iteration order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub(crate) struct TeamRuleSet(IndexMap<String, Vec<PieceColor>>);
+
+impl TeamRuleSet {
+    pub(crate) fn new() -> Self {
+        Self(IndexMap::new())
+    }
+
+    /// Returns all teams with their members.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &[PieceColor])> {
+        self.0
+            .iter()
+            .map(|(name, members)| (name.as_str(), members.as_slice()))
+    }
+
+    /// Returns the name of the team the color belongs to.
+    pub(crate) fn team_of(&self, color: PieceColor) -> Option<&str> {
+        self.iter()
+            .find(|(_, members)| members.contains(&color))
+            .map(|(name, _)| name)
+    }
+
+    /// Returns if two colors play on the same team.
+    pub(crate) fn same_team(&self, a: PieceColor, b: PieceColor) -> bool {
+        a == b || matches!((self.team_of(a), self.team_of(b)), (Some(x), Some(y)) if x == y)
+    }
+
+    /// Returns if a team goes by the name: a declared team, or a player on their own.
+    pub(crate) fn has_team(&self, name: &str, players: &PlayerRuleSet) -> bool {
+        self.0.contains_key(name)
+            || players
+                .iter()
+                .any(|(color, _)| color.name() == name && self.team_of(color).is_none())
+    }
+
+    /// Parses from a ron string.
+    pub(crate) fn from_ron_str(str: &str) -> Result<Self, RulesError> {
+        from_ron_str(str)
+    }
+
+    /// Converts into a ron string.
+    pub(crate) fn to_ron_str(&self) -> Result<String, RulesError> {
+        to_ron_str(self)
+    }
+}
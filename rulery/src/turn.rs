@@ -0,0 +1,162 @@
+use crate::expr::{Context, boolean::BoolExpr};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Order in which players take turns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub enum TurnOrder {
+    /// Players take turns in declaration order.
+    #[default]
+    RoundRobin,
+
+    /// The order is reversed every other round (A B C, C B A, ...).
+    Snake,
+
+    /// The next player is the first one, in declaration order after the current player,
+    /// for which the expression holds. The candidate is exposed as `CandidateColor`.
+    #[schemars(extend("x-phase" = "turn_order"))]
+    Custom(BoolExpr),
+}
+
+/// Dice rolled once at the start of every turn.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct Dice {
+    /// Number of dice rolled.
+    #[serde(default = "default_dice_count")]
+    count: u32,
+
+    /// Number of faces on each die, numbered from 1.
+    #[serde(default = "default_dice_faces")]
+    faces: u32,
+}
+
+impl Dice {
+    /// Creates new dice.
+    pub fn new(count: u32, faces: u32) -> Self {
+        Self { count, faces }
+    }
+
+    /// Returns the number of dice rolled.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the number of faces on each die.
+    pub fn faces(&self) -> u32 {
+        self.faces
+    }
+
+    /// Returns if the dice can be rolled.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.count > 0 && self.faces > 0
+    }
+}
+
+/// Turn schedule of the game.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TurnSchedule {
+    /// Order in which players take turns.
+    #[serde(default)]
+    order: TurnOrder,
+
+    /// Number of actions per turn, indexed by turn number.
+    ///
+    /// The last entry applies to all later turns, e.g. `[1, 2]` for Connect6.
+    #[serde(default = "default_actions_per_turn")]
+    actions_per_turn: Vec<usize>,
+
+    /// Whether the first player is picked at random instead of the first declared one.
+    #[serde(default)]
+    random_first_player: bool,
+
+    /// Dice rolled at the start of every turn, exposed as `DiceValue`.
+    #[serde(default)]
+    dice: Option<Dice>,
+}
+
+impl TurnSchedule {
+    /// Creates a new turn schedule.
+    pub fn new(order: TurnOrder, actions_per_turn: Vec<usize>) -> Self {
+        Self {
+            order,
+            actions_per_turn,
+            random_first_player: false,
+            dice: None,
+        }
+    }
+
+    /// Sets the dice rolled at the start of every turn.
+    pub fn set_dice(&mut self, dice: Option<Dice>) {
+        self.dice = dice;
+    }
+
+    /// Sets whether the first player is picked at random.
+    pub fn set_random_first_player(&mut self, random: bool) {
+        self.random_first_player = random;
+    }
+
+    /// Returns the turn order.
+    pub fn order(&self) -> &TurnOrder {
+        &self.order
+    }
+
+    /// Returns the dice rolled at the start of every turn.
+    pub fn dice(&self) -> Option<&Dice> {
+        self.dice.as_ref()
+    }
+
+    /// Returns if the first player is picked at random.
+    pub fn random_first_player(&self) -> bool {
+        self.random_first_player
+    }
+
+    /// Returns the number of actions in the given turn, starting from 1.
+    pub fn actions_in_turn(&self, turn_number: i64) -> usize {
+        let index = usize::try_from(turn_number - 1).unwrap_or(0);
+
+        self.actions_per_turn
+            .get(index)
+            .or(self.actions_per_turn.last())
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Evaluates whether the candidate player can take the next turn.
+    pub fn is_next_player<C>(&self, ctx: &C) -> Result<bool, C::Error>
+    where
+        C: Context,
+    {
+        match &self.order {
+            TurnOrder::Custom(expr) => expr.evaluate(ctx),
+            TurnOrder::RoundRobin | TurnOrder::Snake => Ok(true),
+        }
+    }
+
+    /// Returns if every turn has at least one action.
+    pub(crate) fn is_valid(&self) -> bool {
+        !self.actions_per_turn.is_empty() && !self.actions_per_turn.contains(&0)
+    }
+
+    /// Returns if the dice, if any, can be rolled.
+    pub(crate) fn is_dice_valid(&self) -> bool {
+        self.dice.as_ref().is_none_or(Dice::is_valid)
+    }
+}
+
+impl Default for TurnSchedule {
+    fn default() -> Self {
+        Self::new(TurnOrder::default(), default_actions_per_turn())
+    }
+}
+
+fn default_actions_per_turn() -> Vec<usize> {
+    vec![1]
+}
+
+fn default_dice_count() -> u32 {
+    1
+}
+
+fn default_dice_faces() -> u32 {
+    6
+}
@@ -1,43 +1,92 @@
 use crate::RulesError;
 use ron::{
-    de::from_reader,
     from_str,
     ser::{PrettyConfig, to_string_pretty},
 };
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, DeserializeOwned, Visitor},
+};
 use std::{
-    fs::{self, File},
-    io::BufReader,
-    path::Path,
+    collections::HashSet,
+    fmt,
+    sync::{LazyLock, Mutex},
 };
 
-pub(crate) fn from_ron_str<T>(str: &str) -> Result<T, RulesError>
+/// Names of declared piece kinds and players, leaked once so identifiers stay `Copy`.
+static NAMES: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+/// Returns the interned copy of a name.
+pub(crate) fn intern(name: &str) -> &'static str {
+    let mut names = NAMES.lock().unwrap_or_else(|err| err.into_inner());
+
+    match names.get(name) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into());
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
+/// Returns if a name can be written as a bare identifier.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Deserializes a bare identifier into an interned name.
+pub(crate) fn deserialize_name<'de, D>(deserializer: D) -> Result<&'static str, D::Error>
 where
-    T: DeserializeOwned,
+    D: Deserializer<'de>,
 {
-    Ok(from_str(str)?)
+    struct NameVisitor;
+
+    impl Visitor<'_> for NameVisitor {
+        type Value = &'static str;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an identifier")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(intern(v))
+        }
+    }
+
+    deserializer.deserialize_identifier(NameVisitor)
 }
 
-pub(crate) fn to_ron_str<T>(value: &T) -> Result<String, RulesError>
+/// Deserializes a field that is written like a required one, but may be absent.
+///
+/// Use with `#[serde(default)]`, so an absent field is `None`.
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
-    T: Serialize,
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
 {
-    let pretty = PrettyConfig::new().separate_tuple_members(true);
-    Ok(to_string_pretty(value, pretty)?)
+    T::deserialize(deserializer).map(Some)
 }
 
-pub(crate) fn from_ron_file<P, T>(path: P) -> Result<T, RulesError>
+pub(crate) fn from_ron_str<T>(str: &str) -> Result<T, RulesError>
 where
-    P: AsRef<Path>,
     T: DeserializeOwned,
 {
-    Ok(from_reader(BufReader::new(File::open(path)?))?)
+    Ok(from_str(str)?)
 }
 
-pub(crate) fn to_ron_file<T, P>(value: &T, path: P) -> Result<(), RulesError>
+pub(crate) fn to_ron_str<T>(value: &T) -> Result<String, RulesError>
 where
     T: Serialize,
-    P: AsRef<Path>,
 {
-    Ok(fs::write(path, to_ron_str(value)?)?)
+    let pretty = PrettyConfig::new().separate_tuple_members(true);
+    Ok(to_string_pretty(value, pretty)?)
 }
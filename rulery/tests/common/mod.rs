@@ -0,0 +1,21 @@
+use std::{fs, path::PathBuf};
+
+/// The example rules shipped with the crate.
+pub fn example_rules() -> Vec<PathBuf> {
+    ron_files("../example-rules")
+}
+
+/// The RON files in a directory, relative to the crate.
+pub fn ron_files(dir: &str) -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(dir);
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+
+    paths.sort();
+    assert!(!paths.is_empty());
+    paths
+}
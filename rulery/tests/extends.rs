@@ -0,0 +1,260 @@
+use rulery::{RulesError, UncheckedGameRules, count::Count, piece::PieceModel, pos::Pos};
+use std::{fs, path::PathBuf};
+
+const BASE: &str = r#"(
+    name: "Base",
+    board: (rows: 8, cols: 8),
+    pieces: {
+        Pawn: (
+            count: Infinite,
+            movement: True,
+            placement: False,
+            display_name: Some("Pawn"),
+            mesh_file: Some("meshes/pawn.obj"),
+        ),
+    },
+    players: {
+        White: (lose_condition: False, win_condition: False),
+    },
+    initial_layout: [
+        (model: Pawn, color: White, pos: (0, 0)),
+        (model: Pawn, color: White, pos: (1, 1)),
+    ],
+    game_over_condition: False,
+)"#;
+
+fn tempdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rulery-extends-{name}-{}", std::process::id()));
+    fs::create_dir_all(dir.join("variants")).unwrap();
+    dir
+}
+
+#[test]
+fn variant_overrides_parts_of_its_base() {
+    let dir = tempdir("override");
+    fs::write(dir.join("base.ron"), BASE).unwrap();
+    fs::write(
+        dir.join("variants/small.ron"),
+        r#"(
+    extends: "../base.ron",
+    name: "Small",
+    board: (rows: 5),
+    pieces: {
+        Pawn: (movement: False),
+        Queen: (count: Finite(1), movement: True, placement: True),
+    },
+    initial_layout: [
+        (model: Queen, color: White, pos: (1, 1)),
+    ],
+    game_over_condition: True,
+)"#,
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variants/small.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    assert_eq!(rules.name(), "Small");
+    assert_eq!((rules.board_rows(), rules.board_cols()), (5, 8));
+
+    let models: Vec<_> = rules.pieces().map(|(model, _)| model.name()).collect();
+    assert_eq!(models, ["Pawn", "Queen"]);
+
+    // Untouched fields of an overridden piece are inherited.
+    let pawn = PieceModel::new("Pawn");
+    assert_eq!(rules.model_name(pawn), "Pawn");
+    assert_eq!(
+        rules.model_mesh_file(pawn),
+        Some(dir.join("variants/../meshes/pawn.obj"))
+    );
+
+    let layout: Vec<_> = rules
+        .initial_pieces()
+        .map(|piece| (piece.model().name(), piece.pos()))
+        .collect();
+    assert_eq!(
+        layout,
+        [("Pawn", Pos::new(0, 0)), ("Queen", Pos::new(1, 1))]
+    );
+
+    assert_eq!(rules.extends(), Some("../base.ron".as_ref()));
+    assert!(!rules.is_inherited("board"));
+    assert!(!rules.is_inherited("game_over_condition"));
+    assert!(rules.is_inherited("players"));
+    assert!(rules.is_inherited("draw_condition"));
+}
+
+#[test]
+fn variants_extend_variants() {
+    let dir = tempdir("chain");
+    fs::write(dir.join("base.ron"), BASE).unwrap();
+    fs::write(
+        dir.join("variants/small.ron"),
+        r#"(extends: "../base.ron", board: (rows: 5, cols: 5))"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("variants/tiny.ron"),
+        r#"(extends: "small.ron", name: "Tiny", board: (cols: 3))"#,
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variants/tiny.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    assert_eq!(rules.name(), "Tiny");
+    assert_eq!((rules.board_rows(), rules.board_cols()), (5, 3));
+    assert!(!rules.is_inherited("name"));
+}
+
+#[test]
+fn cycles_are_rejected() {
+    let dir = tempdir("cycle");
+    fs::write(dir.join("a.ron"), r#"(extends: "b.ron")"#).unwrap();
+    fs::write(dir.join("b.ron"), r#"(extends: "a.ron")"#).unwrap();
+
+    let err = UncheckedGameRules::load(dir.join("a.ron")).unwrap_err();
+
+    let RulesError::InFile(_, err) = err else {
+        panic!("{err}");
+    };
+
+    let RulesError::InFile(_, err) = *err else {
+        panic!("{err}");
+    };
+
+    assert!(matches!(*err, RulesError::ExtendsCycle(path) if path == dir.join("a.ron")));
+}
+
+#[test]
+fn new_pieces_need_rules() {
+    let dir = tempdir("new");
+    fs::write(dir.join("base.ron"), BASE).unwrap();
+    fs::write(
+        dir.join("variant.ron"),
+        r#"(extends: "base.ron", pieces: { Queen: (movement: True) })"#,
+    )
+    .unwrap();
+
+    let err = UncheckedGameRules::load(dir.join("variant.ron")).unwrap_err();
+
+    assert!(matches!(
+        err,
+        RulesError::IncompletePiece(path, model)
+            if path == dir.join("variant.ron") && model.name() == "Queen"
+    ));
+}
+
+#[test]
+fn variant_removes_time_control() {
+    let dir = tempdir("untimed");
+    fs::write(
+        dir.join("base.ron"),
+        BASE.replace(
+            "game_over_condition: False,",
+            "time_control: Some((initial_millis: 60000)),\n    game_over_condition: False,",
+        ),
+    )
+    .unwrap();
+    fs::write(dir.join("timed.ron"), r#"(extends: "base.ron")"#).unwrap();
+    fs::write(
+        dir.join("untimed.ron"),
+        r#"(extends: "base.ron", time_control: None)"#,
+    )
+    .unwrap();
+
+    let load = |name: &str| {
+        UncheckedGameRules::load(dir.join(name))
+            .unwrap()
+            .check()
+            .unwrap()
+    };
+
+    let timed = load("timed.ron");
+    assert_eq!(
+        timed.time_control().map(|tc| tc.initial_millis()),
+        Some(60000)
+    );
+    assert!(timed.is_inherited("time_control"));
+
+    let untimed = load("untimed.ron");
+    assert!(untimed.time_control().is_none());
+    assert!(!untimed.is_inherited("time_control"));
+}
+
+#[test]
+fn variant_includes_add_to_base_includes() {
+    let dir = tempdir("include");
+    fs::write(
+        dir.join("knight.ron"),
+        r#"(pieces: { Knight: (count: Infinite, movement: True, placement: True) })"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("variants/bishop.ron"),
+        r#"(pieces: { Bishop: (count: Infinite, movement: True, placement: True) })"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("base.ron"),
+        BASE.replace(
+            "name: \"Base\",",
+            "include: [\"knight.ron\"],\n    name: \"Base\",",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("variants/bishops.ron"),
+        r#"(extends: "../base.ron", include: ["bishop.ron"])"#,
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variants/bishops.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let mut models: Vec<_> = rules.pieces().map(|(model, _)| model.name()).collect();
+    models.sort();
+    assert_eq!(models, ["Bishop", "Knight", "Pawn"]);
+}
+
+#[test]
+fn variant_overrides_included_pieces() {
+    let dir = tempdir("include-override");
+    fs::write(dir.join("base.ron"), BASE).unwrap();
+    fs::write(
+        dir.join("variants/pieces.ron"),
+        r#"(pieces: {
+    Knight: (count: Infinite, movement: True, placement: True, display_name: Some("Knight")),
+    Pawn: (count: Finite(8), movement: True, placement: True),
+})"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("variants/knights.ron"),
+        r#"(
+    extends: "../base.ron",
+    include: ["pieces.ron"],
+    pieces: { Knight: (count: Finite(2)) },
+)"#,
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variants/knights.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    // Included kinds replace the base's, then the variant's overrides apply to them.
+    let knight = rules.get_piece(PieceModel::new("Knight")).unwrap();
+    assert!(matches!(knight.count(), Count::Finite(2)));
+    assert_eq!(rules.model_name(PieceModel::new("Knight")), "Knight");
+
+    let pawn = rules.get_piece(PieceModel::new("Pawn")).unwrap();
+    assert!(matches!(pawn.count(), Count::Finite(8)));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::example_rules;
+use rulery::{
+    UncheckedGameRules,
+    format::{Format, convert},
+};
+use std::fs;
+
+#[test]
+fn detects_format_from_extension() {
+    assert_eq!(Format::from_path("rules.ron"), Format::Ron);
+    assert_eq!(Format::from_path("rules.JSON"), Format::Json);
+    assert_eq!(Format::from_path("dir/rules.toml"), Format::Toml);
+    assert_eq!(Format::from_path("rules"), Format::Ron);
+}
+
+#[test]
+fn converts_losslessly() {
+    for path in example_rules() {
+        let ron = fs::read_to_string(&path).unwrap();
+        let expected = convert(&ron, Format::Ron, Format::Ron).unwrap();
+
+        for format in [Format::Json, Format::Toml] {
+            let converted = convert(&ron, Format::Ron, format).unwrap_or_else(|err| {
+                panic!("{} to {format:?}: {err}", path.display());
+            });
+
+            let back = convert(&converted, format, Format::Ron).unwrap();
+
+            assert_eq!(back, expected, "{} via {format:?}", path.display());
+        }
+    }
+}
+
+#[test]
+fn converted_rules_check() {
+    for path in example_rules() {
+        let ron = fs::read_to_string(&path).unwrap();
+
+        for format in [Format::Json, Format::Toml] {
+            let converted = convert(&ron, Format::Ron, format).unwrap();
+
+            UncheckedGameRules::parse(&converted, format)
+                .unwrap()
+                .check()
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn errors_keep_position() {
+    let err = UncheckedGameRules::parse("{\n  \"name\": 1\n}", Format::Json).unwrap_err();
+    assert!(err.to_string().contains("line 2"), "{err}");
+
+    let err = UncheckedGameRules::parse("name = \"Test\"\nboard = 1\n", Format::Toml).unwrap_err();
+    assert!(err.to_string().contains("line 2"), "{err}");
+}
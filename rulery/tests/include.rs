@@ -0,0 +1,118 @@
+use rulery::{RulesError, UncheckedGameRules, piece::PieceModel};
+use std::{fs, path::PathBuf};
+
+const PIECES: &str = r#"(
+    pieces: {
+        Pawn: (
+            count: Infinite,
+            movement: True,
+            placement: False,
+            mesh_file: Some("meshes/pawn.obj"),
+        ),
+    },
+)"#;
+
+const PLAYERS: &str = r#"(
+    players: {
+        White: (lose_condition: False, win_condition: False),
+        Black: (lose_condition: False, win_condition: False),
+    },
+)"#;
+
+fn rules(include: &str, pieces: &str) -> String {
+    format!(
+        r#"(
+    include: [{include}],
+    name: "Variant",
+    board: (rows: 8, cols: 8),
+    pieces: {{{pieces}}},
+    players: {{}},
+    initial_layout: [],
+    game_over_condition: False,
+)"#
+    )
+}
+
+fn tempdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rulery-include-{name}-{}", std::process::id()));
+    fs::create_dir_all(dir.join("common")).unwrap();
+    dir
+}
+
+#[test]
+fn merges_included_pieces_and_players() {
+    let dir = tempdir("merge");
+    fs::write(dir.join("common/pieces.ron"), PIECES).unwrap();
+    fs::write(dir.join("common/players.ron"), PLAYERS).unwrap();
+    fs::write(
+        dir.join("variant.ron"),
+        rules(
+            r#""common/pieces.ron", "common/players.ron""#,
+            "Queen: (count: Finite(1), movement: True, placement: False)",
+        ),
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variant.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let models: Vec<_> = rules.pieces().map(|(model, _)| model.name()).collect();
+    assert_eq!(models, ["Pawn", "Queen"]);
+    assert_eq!(rules.players().count(), 2);
+
+    // Asset files stay relative to the included file.
+    assert_eq!(
+        rules.model_mesh_file(PieceModel::new("Pawn")),
+        Some(dir.join("common/meshes/pawn.obj"))
+    );
+}
+
+#[test]
+fn merges_nested_includes_once() {
+    let dir = tempdir("nested");
+    fs::write(dir.join("common/pieces.ron"), PIECES).unwrap();
+    fs::write(dir.join("common/players.ron"), PLAYERS).unwrap();
+    fs::write(
+        dir.join("common/all.ron"),
+        r#"(include: ["pieces.ron", "players.ron"])"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("variant.ron"),
+        rules(r#""common/all.ron", "common/pieces.ron""#, ""),
+    )
+    .unwrap();
+
+    let rules = UncheckedGameRules::load(dir.join("variant.ron"))
+        .unwrap()
+        .check()
+        .unwrap();
+
+    assert_eq!(rules.pieces().count(), 1);
+    assert_eq!(rules.players().count(), 2);
+}
+
+#[test]
+fn conflicts_name_the_file() {
+    let dir = tempdir("conflict");
+    fs::write(dir.join("common/pieces.ron"), PIECES).unwrap();
+    fs::write(
+        dir.join("variant.ron"),
+        rules(
+            r#""common/pieces.ron""#,
+            "Pawn: (count: Finite(8), movement: True, placement: False)",
+        ),
+    )
+    .unwrap();
+
+    let err = UncheckedGameRules::load(dir.join("variant.ron")).unwrap_err();
+
+    let RulesError::InFile(path, err) = err else {
+        panic!("{err}");
+    };
+
+    assert_eq!(path, dir.join("variant.ron"));
+    assert!(matches!(*err, RulesError::DuplicateModel(model) if model.name() == "Pawn"));
+}
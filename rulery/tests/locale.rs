@@ -0,0 +1,44 @@
+use rulery::{
+    UncheckedGameRules,
+    locale::LocalizedText,
+    piece::{PieceColor, PieceModel},
+};
+use std::path::PathBuf;
+
+#[test]
+fn falls_back_to_language_then_first() {
+    let text =
+        LocalizedText::from_ron_str(r#"{"en": "Chariot", "zh-CN": "车", "zh-TW": "車"}"#).unwrap();
+
+    assert_eq!(text.get("zh-TW"), "車");
+    assert_eq!(text.get("ZH-tw"), "車");
+    assert_eq!(text.get("zh"), "车");
+    assert_eq!(text.get("ja"), "Chariot");
+    assert_eq!(text.fallback(), "Chariot");
+
+    let plain = LocalizedText::from_ron_str(r#""Chariot""#).unwrap();
+    assert_eq!(plain.get("zh"), "Chariot");
+}
+
+#[test]
+fn xiangqi_texts_follow_the_locale() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../example-rules/xiangqi.ron");
+    let load = |locale: &str| {
+        let mut unchecked = UncheckedGameRules::load(&path).unwrap();
+        unchecked.set_locale(locale);
+        unchecked.check().unwrap()
+    };
+
+    let rules = UncheckedGameRules::load(&path).unwrap().check().unwrap();
+    assert_eq!(rules.name(), "Xiangqi (Chinese Chess)");
+
+    let rules = load("zh");
+    assert_eq!(rules.locale(), "zh");
+    assert_eq!(rules.name(), "中国象棋");
+    assert_eq!(rules.model_name(PieceModel::new("Cube")), "车");
+    assert_eq!(rules.color_name(PieceColor::new("Red")), "红方");
+
+    let rules = load("ja-JP");
+    assert_eq!(rules.model_name(PieceModel::new("Cube")), "車");
+    assert!(rules.how_to_play().unwrap().starts_with("# 遊び方"));
+}
@@ -0,0 +1,32 @@
+use rulery::{CheckedGameRules, UncheckedGameRules, format::Format};
+use std::path::PathBuf;
+
+#[test]
+fn reads_metadata() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../example-rules/xiangqi.ron");
+    let rules = UncheckedGameRules::load(path).unwrap().check().unwrap();
+    let metadata = rules.metadata();
+
+    assert_eq!(metadata.license(), Some("MIT"));
+    assert_eq!(metadata.recommended_players(), [2]);
+    assert!(metadata.tags().iter().any(|tag| tag == "chess-like"));
+    assert!(rules.how_to_play().unwrap().starts_with("# How to Play"));
+    assert!(metadata.authors().is_empty());
+}
+
+#[test]
+fn empty_metadata_is_not_written() {
+    let rules = CheckedGameRules::default();
+    assert!(rules.metadata().is_empty());
+
+    let path = std::env::temp_dir().join(format!("rulery-metadata-{}.json", std::process::id()));
+    rules.save(&path).unwrap();
+
+    let json = std::fs::read_to_string(&path).unwrap();
+    assert!(!json.contains("metadata"));
+
+    UncheckedGameRules::parse(&json, Format::Json)
+        .unwrap()
+        .check()
+        .unwrap();
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{example_rules, ron_files};
+use rulery::{
+    RulesError, UncheckedGameRules,
+    migration::{CURRENT_VERSION, upgrade, version_of},
+};
+use std::{fs, path::PathBuf};
+
+/// Copies of the example rules as written in version 1, kept as they were.
+fn v1_fixtures() -> Vec<PathBuf> {
+    ron_files("tests/fixtures/v1")
+}
+
+#[test]
+fn fixtures_are_version_1() {
+    for path in v1_fixtures() {
+        let ron = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(version_of(&ron).unwrap(), 1, "{}", path.display());
+    }
+}
+
+#[test]
+fn fixtures_load_and_check() {
+    for path in v1_fixtures().into_iter().chain(example_rules()) {
+        let rules = UncheckedGameRules::load(&path).unwrap();
+
+        rules
+            .check()
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+    }
+}
+
+#[test]
+fn fixtures_upgrade_to_current_version() {
+    let dir = tempdir("upgrade");
+
+    for path in v1_fixtures() {
+        let ron = fs::read_to_string(&path).unwrap();
+        let upgraded = upgrade(&ron).unwrap();
+
+        assert_eq!(version_of(&upgraded).unwrap(), CURRENT_VERSION);
+
+        // The upgraded document loads into the same rules as the original.
+        let upgraded_path = dir.join(path.file_name().unwrap());
+        fs::write(&upgraded_path, &upgraded).unwrap();
+
+        let original = save_to_string(&path, &dir.join("original.ron"));
+        let migrated = save_to_string(&upgraded_path, &dir.join("migrated.ron"));
+
+        assert_eq!(original, migrated, "{}", path.display());
+    }
+}
+
+#[test]
+fn saved_rules_are_current() {
+    let dir = tempdir("saved");
+
+    for path in example_rules() {
+        let saved = save_to_string(&path, &dir.join("saved.ron"));
+
+        assert_eq!(version_of(&saved).unwrap(), CURRENT_VERSION);
+        assert_eq!(upgrade(&saved).unwrap(), saved);
+    }
+}
+
+#[test]
+fn newer_version_is_rejected() {
+    let ron = format!("(version: {}, name: \"Future\")", CURRENT_VERSION + 1);
+
+    assert!(matches!(
+        upgrade(&ron),
+        Err(RulesError::UnsupportedVersion(version)) if version == CURRENT_VERSION + 1
+    ));
+}
+
+/// Loads, checks and saves rules, returning the saved text.
+fn save_to_string(path: &PathBuf, out: &PathBuf) -> String {
+    UncheckedGameRules::load(path)
+        .unwrap()
+        .check()
+        .unwrap()
+        .save(out)
+        .unwrap();
+
+    fs::read_to_string(out).unwrap()
+}
+
+fn tempdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rulery-migration-{name}-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
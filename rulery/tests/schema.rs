@@ -0,0 +1,23 @@
+use rulery::schema::{PHASES, json_schema};
+use std::{fs, path::PathBuf};
+
+#[test]
+fn editor_schema_is_current() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../editor/rules.schema.json");
+    let shipped = fs::read_to_string(&path).unwrap();
+
+    assert!(
+        shipped == json_schema().unwrap(),
+        "{} is outdated, regenerate it with `rulery::schema::json_schema`",
+        path.display()
+    );
+}
+
+#[test]
+fn schema_names_every_phase() {
+    let schema = json_schema().unwrap();
+
+    for phase in PHASES {
+        assert!(schema.contains(&format!("\"{phase}\"")), "{phase}");
+    }
+}
@@ -0,0 +1,48 @@
+use rulery::{RulesError, UncheckedGameRules, format::Format};
+
+fn rules(game_over_condition: &str) -> String {
+    format!(
+        r#"(
+    name: "Teams",
+    board: (rows: 4, cols: 4),
+    pieces: {{
+        Stone: (count: Infinite, movement: False, placement: True),
+    }},
+    players: {{
+        White: (lose_condition: False, win_condition: False),
+        Black: (lose_condition: False, win_condition: False),
+        Red: (lose_condition: False, win_condition: False),
+    }},
+    teams: {{
+        "Light": [White],
+        "Dark": [Black],
+    }},
+    initial_layout: [],
+    game_over_condition: {game_over_condition},
+)"#
+    )
+}
+
+fn check(game_over_condition: &str) -> Result<(), RulesError> {
+    UncheckedGameRules::parse(&rules(game_over_condition), Format::Ron)
+        .unwrap()
+        .check()
+        .map(|_| ())
+}
+
+#[test]
+fn team_states_name_declared_teams_or_lone_players() {
+    check("TeamStateEqual(\"Light\", Won)").unwrap();
+    check("Or([TeamStateEqual(\"Dark\", Lost), TeamStateEqual(\"Red\", Won)])").unwrap();
+}
+
+#[test]
+fn unknown_team_names_are_rejected() {
+    let err = check("Not(TeamStateEqual(\"Lihgt\", Won))").unwrap_err();
+    assert!(matches!(err, RulesError::NoSuchTeam(name) if name == "Lihgt"));
+
+    // Players on a declared team are known by the team's name only.
+    let err = check("Equal(If(TeamStateEqual(\"White\", Won), Const(1), Const(0)), Const(1))")
+        .unwrap_err();
+    assert!(matches!(err, RulesError::NoSuchTeam(name) if name == "White"));
+}